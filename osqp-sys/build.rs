@@ -1,31 +1,67 @@
+extern crate cc;
 extern crate cmake;
+#[cfg(feature = "system")]
+extern crate pkg_config;
 use cmake::Config;
 
 use std::env;
 use std::fs;
 use std::path::Path;
+#[cfg(feature = "system")]
+use std::path::PathBuf;
 use std::process::Command;
 
 fn main() {
-    if !Path::new("osqp/README.md").exists() {
-        let _ = Command::new("git")
-            .args(&["submodule", "update", "--init", "--recursive"])
-            .status();
-    }
-
-    // Try to make c_int the same size as the target pointer width (i.e. 32 or 64 bits)
+    // Try to make c_int the same size as the target pointer width (i.e. 32 or 64 bits) unless
+    // 32-bit indices are requested
     println!("cargo:rustc-check-cfg=cfg(osqp_dlong)");
+    println!("cargo:rustc-check-cfg=cfg(osqp_printing)");
+    println!("cargo:rustc-check-cfg=cfg(osqp_profiling)");
+    let int32_enabled = env::var_os("CARGO_FEATURE_I32").is_some();
     let dlong_enabled = match &*env::var("CARGO_CFG_TARGET_POINTER_WIDTH").unwrap() {
-        "64" => {
+        "64" if !int32_enabled => {
             println!("cargo:rustc-cfg=osqp_dlong");
-            "ON"
+            true
         }
-        "32" => "OFF",
+        "64" | "32" => false,
         other => panic!(
             "{} bit targets are not supported. If you want this feature please file a bug.",
             other
         ),
     };
+    let float_enabled = env::var_os("CARGO_FEATURE_F32").is_some();
+    let interrupt_enabled = env::var_os("CARGO_FEATURE_INTERRUPT").is_some();
+    let printing_enabled = env::var_os("CARGO_FEATURE_PRINTING").is_some();
+    let profiling_enabled = env::var_os("CARGO_FEATURE_PROFILING").is_some();
+
+    if env::var_os("CARGO_FEATURE_SYSTEM").is_some() {
+        if interrupt_enabled {
+            panic!("the `interrupt` feature cannot be used when linking a system OSQP library");
+        }
+        link_system(dlong_enabled, float_enabled);
+    } else {
+        build_vendored(
+            dlong_enabled,
+            float_enabled,
+            interrupt_enabled,
+            printing_enabled,
+            profiling_enabled,
+        );
+    }
+}
+
+fn build_vendored(
+    dlong_enabled: bool,
+    float_enabled: bool,
+    interrupt_enabled: bool,
+    printing_enabled: bool,
+    profiling_enabled: bool,
+) {
+    if !Path::new("osqp/README.md").exists() {
+        let _ = Command::new("git")
+            .args(&["submodule", "update", "--init", "--recursive"])
+            .status();
+    }
 
     // The CMake build script for OSQP generates files inside the source directory.
     // The docs.rs builder does not like this, so we copy the OSQP source tree into `OUT_DIR`.
@@ -50,14 +86,56 @@ fn main() {
     )
     .expect("failed to copy OSQP sources to `OUT_DIR`");
 
+    if interrupt_enabled {
+        // Replace OSQP's interrupt listener, which installs a SIGINT handler, with one that is
+        // controlled from Rust (see `shim/interrupt.c`).
+        let listener = ["src/interrupt.c", "src/ctrlc.c"]
+            .iter()
+            .map(|path| src_dir.join(path))
+            .find(|path| path.exists())
+            .expect("failed to find the OSQP interrupt listener sources");
+        fs::copy("shim/interrupt.c", listener)
+            .expect("failed to replace the OSQP interrupt listener");
+    }
+
+    // Code generation can copy the OSQP sources alongside the generated files
+    println!("cargo:rustc-env=OSQP_SOURCE_DIR={}", src_dir.display());
+
     fs::create_dir_all(build_dir).expect("failed to create OSQP build directory in `OUT_DIR`");
 
-    Config::new(&src_dir)
-        .define("OSQP_ENABLE_INTERRUPT", "OFF")
-        .define("OSQP_USE_FLOAT", "OFF")
-        .define("OSQP_USE_LONG", dlong_enabled)
-        .define("OSQP_ENABLE_PRINTING", "ON")
-        .define("OSQP_ENABLE_PROFILING", "ON")
+    // All output printed by OSQP is forwarded to Rust (see `src/printing.rs`) and all memory is
+    // allocated by the Rust global allocator (see `src/memory.rs`)
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let shim_dir = Path::new(&manifest_dir).join("shim");
+    let printing_header = shim_dir.join("printing.h");
+    let memory_header = shim_dir.join("memory.h");
+
+    let mut config = Config::new(&src_dir);
+    if printing_enabled {
+        println!("cargo:rustc-cfg=osqp_printing");
+        config.define("OSQP_CUSTOM_PRINTING", &printing_header);
+    }
+    if profiling_enabled {
+        println!("cargo:rustc-cfg=osqp_profiling");
+    }
+    config
+        .define(
+            "OSQP_ENABLE_INTERRUPT",
+            if interrupt_enabled { "ON" } else { "OFF" },
+        )
+        .define("OSQP_USE_FLOAT", if float_enabled { "ON" } else { "OFF" })
+        .define("OSQP_USE_LONG", if dlong_enabled { "ON" } else { "OFF" })
+        .define(
+            "OSQP_ENABLE_PRINTING",
+            if printing_enabled { "ON" } else { "OFF" },
+        )
+        .define("OSQP_CUSTOM_MEMORY", &memory_header)
+        .define(
+            "OSQP_ENABLE_PROFILING",
+            if profiling_enabled { "ON" } else { "OFF" },
+        )
+        .define("OSQP_ENABLE_DERIVATIVES", "ON")
+        .define("OSQP_CODEGEN", "ON")
         .define("OSQP_BUILD_UNITTESTS", "OFF")
         // Ensure build outputs are always in `build_dir` whichever generator CMake uses
         .define("CMAKE_ARCHIVE_OUTPUT_DIRECTORY", &build_dir)
@@ -70,4 +148,137 @@ fn main() {
 
     println!("cargo:rustc-link-search=native={}", build_dir);
     println!("cargo:rustc-link-lib=static=osqpstatic");
+
+    // Compiled after emitting the OSQP link directives so the shim follows `osqpstatic` on the
+    // linker command line
+    if printing_enabled {
+        cc::Build::new()
+            .file(shim_dir.join("printing.c"))
+            .compile("osqp_rs_shim");
+    }
+}
+
+/// Links a preinstalled OSQP library found using `OSQP_LIB_DIR` or pkg-config.
+#[cfg(feature = "system")]
+fn link_system(dlong_enabled: bool, float_enabled: bool) {
+    for var in &["OSQP_LIB_DIR", "OSQP_INCLUDE_DIR", "OSQP_STATIC"] {
+        println!("cargo:rerun-if-env-changed={}", var);
+    }
+    let statik = env::var_os("OSQP_STATIC").map_or(false, |value| value != "0");
+    let include_dir = env::var_os("OSQP_INCLUDE_DIR").map(PathBuf::from);
+
+    let include_dirs = match env::var_os("OSQP_LIB_DIR") {
+        Some(lib_dir) => {
+            let lib_dir = PathBuf::from(lib_dir);
+            println!("cargo:rustc-link-search=native={}", lib_dir.display());
+            // OSQP installs its static library as `osqpstatic` and its shared library as `osqp`
+            if statik {
+                println!("cargo:rustc-link-lib=static=osqpstatic");
+            } else {
+                println!("cargo:rustc-link-lib=dylib=osqp");
+            }
+            vec![include_dir.unwrap_or_else(|| lib_dir.join("../include"))]
+        }
+        None => {
+            let library = pkg_config::Config::new()
+                .statik(statik)
+                .probe("osqp")
+                .unwrap_or_else(|err| {
+                    panic!(
+                        "failed to find OSQP using pkg-config, set `OSQP_LIB_DIR` to the \
+                         directory containing the library: {}",
+                        err
+                    )
+                });
+            match include_dir {
+                Some(include_dir) => vec![include_dir],
+                None => library.include_paths,
+            }
+        }
+    };
+    check_system_config(&include_dirs, dlong_enabled, float_enabled);
+
+    // The OSQP sources are not available for code generation to copy
+    println!("cargo:rustc-env=OSQP_SOURCE_DIR=");
+}
+
+#[cfg(not(feature = "system"))]
+fn link_system(_dlong_enabled: bool, _float_enabled: bool) {
+    unreachable!()
+}
+
+/// The OSQP version the bindings were generated from. Any patch release is compatible.
+#[cfg(feature = "system")]
+const BINDINGS_VERSION: &str = "1.0.";
+
+/// Checks the configuration of the system OSQP library headers matches the bindings and enables
+/// the printing and profiling support the library was built with.
+#[cfg(feature = "system")]
+fn check_system_config(include_dirs: &[PathBuf], dlong_enabled: bool, float_enabled: bool) {
+    let probe = Path::new(&env::var("OUT_DIR").unwrap()).join("osqp_config_probe.c");
+    fs::write(
+        &probe,
+        "#include \"osqp_configure.h\"\n\
+         osqp_rs_version OSQP_VERSION\n\
+         #ifdef OSQP_USE_LONG\nosqp_rs_long 1\n#else\nosqp_rs_long 0\n#endif\n\
+         #ifdef OSQP_USE_FLOAT\nosqp_rs_float 1\n#else\nosqp_rs_float 0\n#endif\n\
+         #ifdef OSQP_ENABLE_PRINTING\nosqp_rs_printing 1\n#else\nosqp_rs_printing 0\n#endif\n\
+         #ifdef OSQP_ENABLE_PROFILING\nosqp_rs_profiling 1\n#else\nosqp_rs_profiling 0\n#endif\n",
+    )
+    .expect("failed to write the OSQP configuration probe");
+
+    let mut build = cc::Build::new();
+    build.file(&probe);
+    for dir in include_dirs {
+        // Headers are installed in an `osqp` subdirectory of the include directory
+        build.include(dir).include(dir.join("osqp"));
+    }
+    let expanded = build.expand();
+    let expanded = String::from_utf8_lossy(&expanded);
+    let value = |name: &str| {
+        expanded
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix(name))
+            .map(|value| value.trim().trim_matches(&['(', ')', '"'][..]).to_owned())
+            .next()
+            .unwrap_or_else(|| panic!("failed to read `{}` from the OSQP headers", name))
+    };
+
+    let version = value("osqp_rs_version");
+    if !version.starts_with(BINDINGS_VERSION) {
+        panic!(
+            "the system OSQP library has version {} but the bindings require version {}x",
+            version, BINDINGS_VERSION
+        );
+    }
+    let int_bits = if value("osqp_rs_long") == "1" { 64 } else { 32 };
+    let expected_int_bits = if dlong_enabled { 64 } else { 32 };
+    if int_bits != expected_int_bits {
+        panic!(
+            "the system OSQP library uses {} bit integers but the bindings require {} bit \
+             integers (see the `i32` feature)",
+            int_bits, expected_int_bits
+        );
+    }
+    let float_bits = if value("osqp_rs_float") == "1" {
+        32
+    } else {
+        64
+    };
+    let expected_float_bits = if float_enabled { 32 } else { 64 };
+    if float_bits != expected_float_bits {
+        panic!(
+            "the system OSQP library uses {} bit floats but the bindings require {} bit floats \
+             (see the `f32` feature)",
+            float_bits, expected_float_bits
+        );
+    }
+
+    // The `printing` and `profiling` features cannot change a prebuilt library
+    if value("osqp_rs_printing") == "1" {
+        println!("cargo:rustc-cfg=osqp_printing");
+    }
+    if value("osqp_rs_profiling") == "1" {
+        println!("cargo:rustc-cfg=osqp_profiling");
+    }
 }
@@ -9,6 +9,7 @@ bindgen bindings.h -o src/bindings.rs \
 --raw-line "use {OSQPFloat, OSQPInt};" \
 --allowlist-type osqp_linsys_solver_type \
 --allowlist-type osqp_precond_type \
+--allowlist-type osqp_adaptive_rho_update_type \
 --allowlist-type OSQPWorkspace_ \
 --allowlist-type OSQPSolver \
 --allowlist-type OSQPCscMatrix \
@@ -17,6 +18,8 @@ bindgen bindings.h -o src/bindings.rs \
 --allowlist-function OSQPSettings_new \
 --allowlist-function OSQPSettings_free \
 --allowlist-function osqp_version \
+--allowlist-function osqp_capabilities \
+--allowlist-type osqp_capabilities_type \
 --allowlist-type osqp_status_type \
 --allowlist-function osqp_update_data_vec \
 --allowlist-function osqp_update_data_mat \
@@ -25,6 +28,7 @@ bindgen bindings.h -o src/bindings.rs \
 --allowlist-type OSQPScaling \
 --allowlist-type OSQPSolution \
 --allowlist-type OSQPInfo \
+--allowlist-type OSQPCodegenDefines \
 --allowlist-type OSQPPolish \
 --allowlist-type OSQPData \
 --allowlist-type OSQPSettings \
@@ -39,6 +43,7 @@ bindgen bindings.h -o src/bindings.rs \
 --allowlist-function osqp_warm_start \
 --allowlist-function osqp_warm_start_x \
 --allowlist-function osqp_warm_start_y \
+--allowlist-function osqp_cold_start \
 --allowlist-function osqp_update_rho \
 --allowlist-function osqp_update_max_iter \
 --allowlist-function osqp_update_eps_abs \
@@ -56,3 +61,8 @@ bindgen bindings.h -o src/bindings.rs \
 --allowlist-function osqp_update_time_limit \
 --allowlist-function osqp_set_default_settings \
 --allowlist-function osqp_update_settings \
+--allowlist-function osqp_adjoint_derivative_compute \
+--allowlist-function osqp_adjoint_derivative_get_mat \
+--allowlist-function osqp_adjoint_derivative_get_vec \
+--allowlist-function osqp_set_default_codegen_defines \
+--allowlist-function osqp_codegen \
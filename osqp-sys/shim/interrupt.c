@@ -0,0 +1,17 @@
+/*
+ * Replacement for the OSQP interrupt listener, used when the `interrupt` feature is enabled.
+ *
+ * The listener shipped with OSQP installs a process-wide SIGINT handler. This version installs
+ * no signal handlers and instead asks the Rust side whether the solve running on the current
+ * thread has been cancelled (see `osqp_sys::interrupt`).
+ */
+
+int osqp_rs_is_interrupted(void);
+
+void osqp_start_interrupt_listener(void) {}
+
+void osqp_end_interrupt_listener(void) {}
+
+int osqp_is_interrupted(void) {
+    return osqp_rs_is_interrupted();
+}
@@ -0,0 +1,22 @@
+/*
+ * Custom memory header for OSQP, passed to CMake as `OSQP_CUSTOM_MEMORY`.
+ *
+ * All allocations are made by the Rust global allocator (see `osqp_sys::memory`).
+ */
+
+#ifndef OSQP_RS_MEMORY_H
+#define OSQP_RS_MEMORY_H
+
+#include <stddef.h>
+
+void* osqp_rs_malloc(size_t size);
+void* osqp_rs_calloc(size_t num, size_t size);
+void* osqp_rs_realloc(void* ptr, size_t new_size);
+void osqp_rs_free(void* ptr);
+
+#define c_malloc osqp_rs_malloc
+#define c_calloc osqp_rs_calloc
+#define c_realloc osqp_rs_realloc
+#define c_free osqp_rs_free
+
+#endif
@@ -0,0 +1,41 @@
+/*
+ * Formats OSQP's output and forwards it to Rust (see `osqp_sys::printing`).
+ */
+
+#include <stdarg.h>
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "printing.h"
+
+void osqp_rs_print(const char* msg, size_t len);
+
+void osqp_rs_printf(const char* format, ...) {
+    char buffer[256];
+    char* msg = buffer;
+    va_list args;
+    int len;
+
+    va_start(args, format);
+    len = vsnprintf(buffer, sizeof(buffer), format, args);
+    va_end(args);
+    if (len < 0) {
+        return;
+    }
+
+    if ((size_t)len >= sizeof(buffer)) {
+        msg = malloc((size_t)len + 1);
+        if (!msg) {
+            return;
+        }
+        va_start(args, format);
+        vsnprintf(msg, (size_t)len + 1, format, args);
+        va_end(args);
+    }
+
+    osqp_rs_print(msg, (size_t)len);
+
+    if (msg != buffer) {
+        free(msg);
+    }
+}
@@ -0,0 +1,21 @@
+/*
+ * Custom printing header for OSQP, passed to CMake as `OSQP_CUSTOM_PRINTING`.
+ *
+ * All output is formatted in C and forwarded to Rust (see `osqp_sys::printing`).
+ */
+
+#ifndef OSQP_RS_PRINTING_H
+#define OSQP_RS_PRINTING_H
+
+void osqp_rs_printf(const char* format, ...);
+
+#define c_print osqp_rs_printf
+
+#ifndef c_eprint
+#define c_eprint(...)                        \
+    c_print("ERROR in %s: ", __FUNCTION__);  \
+    c_print(__VA_ARGS__);                    \
+    c_print("\n");
+#endif
+
+#endif
@@ -24,6 +24,20 @@ pub const OSQP_NO_PRECONDITIONER: osqp_precond_type = 0;
 pub const OSQP_DIAGONAL_PRECONDITIONER: osqp_precond_type = 1;
 #[doc = " Preconditioners for CG method *"]
 pub type osqp_precond_type = ::std::os::raw::c_uint;
+pub const OSQP_ADAPTIVE_RHO_UPDATE_DISABLED: osqp_adaptive_rho_update_type = 0;
+pub const OSQP_ADAPTIVE_RHO_UPDATE_ITERATIONS: osqp_adaptive_rho_update_type = 1;
+pub const OSQP_ADAPTIVE_RHO_UPDATE_TIME: osqp_adaptive_rho_update_type = 2;
+pub const OSQP_ADAPTIVE_RHO_UPDATE_KKT_ERROR: osqp_adaptive_rho_update_type = 3;
+pub const OSQP_ADAPTIVE_RHO_UPDATE_LAST_VALUE: osqp_adaptive_rho_update_type = 4;
+#[doc = " Adaptive rho update methods *"]
+pub type osqp_adaptive_rho_update_type = ::std::os::raw::c_uint;
+pub const OSQP_CAPABILITY_DIRECT_SOLVER: osqp_capabilities_type = 1;
+pub const OSQP_CAPABILITY_INDIRECT_SOLVER: osqp_capabilities_type = 2;
+pub const OSQP_CAPABILITY_CODEGEN: osqp_capabilities_type = 4;
+pub const OSQP_CAPABILITY_UPDATE_MATRICES: osqp_capabilities_type = 8;
+pub const OSQP_CAPABILITY_DERIVATIVES: osqp_capabilities_type = 16;
+#[doc = " Capabilities of the compiled OSQP library *"]
+pub type osqp_capabilities_type = ::std::os::raw::c_uint;
 pub const OSQP_NO_ERROR: osqp_error_type = 0;
 pub const OSQP_DATA_VALIDATION_ERROR: osqp_error_type = 1;
 pub const OSQP_SETTINGS_VALIDATION_ERROR: osqp_error_type = 2;
@@ -198,6 +212,23 @@ pub struct OSQPSolver {
     #[doc = "< Internal solver workspace (contents not public)"]
     pub work: *mut OSQPWorkspace,
 }
+#[doc = " Structure to hold the settings for the generated code"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct OSQPCodegenDefines {
+    #[doc = "< Embedded mode (1 = vectors only, 2 = vectors+matrices)"]
+    pub embedded_mode: OSQPInt,
+    #[doc = "< Use floats if 1, doubles if 0"]
+    pub float_type: OSQPInt,
+    #[doc = "< Enable printing if 1"]
+    pub printing_enable: OSQPInt,
+    #[doc = "< Enable timing of code sections if 1"]
+    pub profiling_enable: OSQPInt,
+    #[doc = "< Enable interrupt checking if 1"]
+    pub interrupt_enable: OSQPInt,
+    #[doc = "< Enable deriatives if 1"]
+    pub derivatives_enable: OSQPInt,
+}
 extern "C" {
     #[doc = " Allocates a new Compressed-Column-Sparse (CSC) matrix from existing arrays.\n\n This will malloc the new matrix structure, but use the arrays passed in as the\n backing data for the matrix (e.g. not copy the actual matrix data, just reference\n the existing data.)\n\n @note Not available in embedded mode (requires malloc)\n\n @param  m     Number of rows\n @param  n     number of columns\n @param  nzmax Maximum number of nonzero elements\n @param  x     Vector of data\n @param  i     Vector of row indices\n @param  p     Vector of column pointers\n @return       Pointer to new CSC matrix, or null on error"]
     pub fn OSQPCscMatrix_new(
@@ -221,6 +252,10 @@ extern "C" {
     #[doc = " Free an OSQPSettings object.\n\n @note Not available in embedded mode (requires free)\n\n @param settings The settings object to free"]
     pub fn OSQPSettings_free(settings: *mut OSQPSettings);
 }
+extern "C" {
+    #[doc = " Return the capabilities of the OSQP solver.\n\n @return OSQP capabilities as a bit field (see osqp_capabilities_type)"]
+    pub fn osqp_capabilities() -> OSQPInt;
+}
 extern "C" {
     #[doc = " Return OSQP version\n\n @return OSQP version string"]
     pub fn osqp_version() -> *const ::std::os::raw::c_char;
@@ -255,6 +290,10 @@ extern "C" {
         y: *const OSQPFloat,
     ) -> OSQPInt;
 }
+extern "C" {
+    #[doc = " Cold start workspace variables xz and y\n\n @param solver Solver"]
+    pub fn osqp_cold_start(solver: *mut OSQPSolver);
+}
 extern "C" {
     #[doc = " Update problem data vectors\n @param  solver  Solver\n @param  q_new   New linear cost, NULL if none\n @param  l_new   New lower bound, NULL if none\n @param  u_new   New upper bound, NULL if none\n @return         Exitflag for errors (0 if no errors)"]
     pub fn osqp_update_data_vec(
@@ -290,4 +329,42 @@ extern "C" {
         solver: *mut OSQPSolver,
         new_settings: *const OSQPSettings,
     ) -> OSQPInt;
-}
\ No newline at end of file
+}
+extern "C" {
+    #[doc = " Compute internal data structures needed for calculation of the adjoint derivatives of P/q/A/l/u.\n\n @note An optimal solution must be obtained before calling this function.\n\n @param[in] solver Solver\n @param[in] dx     Vector of dx values (observed - true) of length n\n @param[in] dy     Vector of dy values (observed - true) of length m\n @return           Exitflag for errors (0 if no errors)"]
+    pub fn osqp_adjoint_derivative_compute(
+        solver: *mut OSQPSolver,
+        dx: *mut OSQPFloat,
+        dy: *mut OSQPFloat,
+    ) -> OSQPInt;
+}
+extern "C" {
+    #[doc = " Calculate adjoint derivatives of P/A.\n\n @note @ref osqp_adjoint_derivative_compute must be called prior to this function.\n\n @param[in]     solver Solver\n @param[out]    dP     Matrix of dP values (n x n)\n @param[out]    dA     Matrix of dA values (m x n)\n @return               Exitflag for errors (0 if no errors)"]
+    pub fn osqp_adjoint_derivative_get_mat(
+        solver: *mut OSQPSolver,
+        dP: *mut OSQPCscMatrix,
+        dA: *mut OSQPCscMatrix,
+    ) -> OSQPInt;
+}
+extern "C" {
+    #[doc = " Calculate adjoint derivatives of q/l/u.\n\n @note @ref osqp_adjoint_derivative_compute must be called prior to this function.\n\n @param[in]     solver Solver\n @param[out]    dq     Vector of dq values of length n\n @param[out]    dl     Matrix of dl values of length m\n @param[out]    du     Matrix of du values of length m\n @return               Exitflag for errors (0 if no errors)"]
+    pub fn osqp_adjoint_derivative_get_vec(
+        solver: *mut OSQPSolver,
+        dq: *mut OSQPFloat,
+        dl: *mut OSQPFloat,
+        du: *mut OSQPFloat,
+    ) -> OSQPInt;
+}
+extern "C" {
+    #[doc = " Set default codegen define values.\n\n @note The @c defines structure must already be allocated in memory.\n\n @param defines Structure to set to default values."]
+    pub fn osqp_set_default_codegen_defines(defines: *mut OSQPCodegenDefines);
+}
+extern "C" {
+    #[doc = " Generate source files with a statically allocated OSQPSolver structure.\n\n @note @c osqp_setup must be called before a call to this function.\n\n @param  solver      Solver\n @param  output_dir  Path to directory to output the files to.\n                     This string must include the trailing directory separator, and\n                     will be prepended to the filenames.\n @param  file_prefix String prepended to the generated filenames and variable names\n @param  defines     The defines to use in the generated code\n @return             Exitflag for errors (0 if no errors)"]
+    pub fn osqp_codegen(
+        solver: *mut OSQPSolver,
+        output_dir: *const ::std::os::raw::c_char,
+        file_prefix: *const ::std::os::raw::c_char,
+        defines: *mut OSQPCodegenDefines,
+    ) -> OSQPInt;
+}
@@ -0,0 +1,42 @@
+//! Cancellation of running solves.
+//!
+//! When the `interrupt` feature is enabled OSQP is built with a replacement interrupt listener
+//! which, rather than installing a SIGINT handler, checks the flag registered on the current
+//! thread using `with_interrupt_flag`.
+
+use std::cell::Cell;
+use std::os::raw::c_int;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+thread_local! {
+    static INTERRUPT_FLAG: Cell<*const AtomicBool> = const { Cell::new(ptr::null()) };
+}
+
+/// Calls `f` with `flag` registered as the interrupt flag for the current thread.
+///
+/// Any call to `osqp_solve` made by `f` on the current thread terminates with the status
+/// `OSQP_SIGINT` once `flag` is set to `true`.
+pub fn with_interrupt_flag<R, F: FnOnce() -> R>(flag: &AtomicBool, f: F) -> R {
+    struct Restore(*const AtomicBool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            INTERRUPT_FLAG.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(INTERRUPT_FLAG.with(|current| current.replace(flag)));
+    f()
+}
+
+#[no_mangle]
+pub extern "C" fn osqp_rs_is_interrupted() -> c_int {
+    INTERRUPT_FLAG
+        .try_with(|current| {
+            let flag = current.get();
+            // The flag outlives any solve started within `with_interrupt_flag`
+            !flag.is_null() && unsafe { (*flag).load(Ordering::SeqCst) }
+        })
+        .unwrap_or(false) as c_int
+}
@@ -2,15 +2,39 @@
 #![allow(non_snake_case)]
 #![allow(non_upper_case_globals)]
 
+extern crate log;
+
 mod bindings;
 pub use bindings::*;
 
+#[cfg(feature = "interrupt")]
+pub mod interrupt;
+
+pub mod memory;
+pub mod printing;
+
 #[cfg(osqp_dlong)]
 pub type osqp_int = ::std::os::raw::c_longlong;
 #[cfg(not(osqp_dlong))]
 pub type osqp_int = ::std::os::raw::c_int;
+#[cfg(feature = "f32")]
+pub type osqp_float = f32;
+#[cfg(not(feature = "f32"))]
 pub type osqp_float = f64;
 
+/// The directory containing the OSQP sources this crate was built from.
+///
+/// This is located in the build output directory and only exists on the machine which built the
+/// crate. It is empty when linking a system OSQP library using the `system` feature.
+pub const OSQP_SOURCE_DIR: &str = env!("OSQP_SOURCE_DIR");
+
+/// Whether the linked OSQP library was built with printing enabled (see the `printing` feature).
+pub const PRINTING_ENABLED: bool = cfg!(osqp_printing);
+
+/// Whether the linked OSQP library was built with profiling enabled (see the `profiling`
+/// feature).
+pub const PROFILING_ENABLED: bool = cfg!(osqp_profiling);
+
 type OSQPInt = osqp_int;
 type OSQPFloat = osqp_float;
 
@@ -79,7 +103,7 @@ mod tests {
         osqp_solve(solver);
 
         // Check the results
-        let eps = 1e-9;
+        let eps = if cfg!(feature = "f32") { 1e-4 } else { 1e-9 };
         let x = (*(*solver).solution).x;
         let x0 = *x;
         let x1 = *(x.offset(1));
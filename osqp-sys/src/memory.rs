@@ -0,0 +1,200 @@
+//! Allocation of OSQP's memory using the Rust global allocator.
+//!
+//! OSQP is built with a custom memory header (see `shim/memory.h`) which forwards all its
+//! allocations to this module. Each allocation is preceded by a header recording its size and
+//! the counter registered on the current thread using `with_allocation_counter`, if any, so the
+//! memory held by each solver can be tracked.
+
+use std::alloc::{self, Layout};
+use std::cell::Cell;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Size of the header preceding each allocation. This is a multiple of the alignment of
+/// `max_align_t` so the returned pointers are suitably aligned for any C type.
+const HEADER_SIZE: usize = 16;
+
+#[repr(C)]
+struct Header {
+    size: usize,
+    counter: *const AtomicUsize,
+}
+
+thread_local! {
+    static ALLOCATION_COUNTER: Cell<*const AtomicUsize> = const { Cell::new(ptr::null()) };
+}
+
+/// Calls `f` with `counter` registered as the allocation counter for the current thread.
+///
+/// Memory allocated by OSQP on the current thread while `f` runs is added to `counter` and
+/// subtracted when it is freed, whichever thread frees it.
+///
+/// # Safety
+///
+/// `counter` must outlive every allocation made while `f` runs.
+pub unsafe fn with_allocation_counter<R, F: FnOnce() -> R>(counter: &AtomicUsize, f: F) -> R {
+    struct Restore(*const AtomicUsize);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            ALLOCATION_COUNTER.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(ALLOCATION_COUNTER.with(|current| current.replace(counter)));
+    f()
+}
+
+fn layout(size: usize) -> Option<Layout> {
+    let size = size.checked_add(HEADER_SIZE)?;
+    Layout::from_size_align(size, HEADER_SIZE).ok()
+}
+
+unsafe fn allocate(size: usize, zeroed: bool) -> *mut c_void {
+    let layout = match layout(size) {
+        Some(layout) => layout,
+        None => return ptr::null_mut(),
+    };
+    let base = if zeroed {
+        alloc::alloc_zeroed(layout)
+    } else {
+        alloc::alloc(layout)
+    };
+    if base.is_null() {
+        return ptr::null_mut();
+    }
+
+    let counter = ALLOCATION_COUNTER
+        .try_with(|current| current.get())
+        .unwrap_or(ptr::null());
+    if !counter.is_null() {
+        (*counter).fetch_add(size, Ordering::Relaxed);
+    }
+    ptr::write(base as *mut Header, Header { size, counter });
+    base.add(HEADER_SIZE) as *mut c_void
+}
+
+unsafe fn header(ptr: *mut c_void) -> *mut Header {
+    (ptr as *mut u8).sub(HEADER_SIZE) as *mut Header
+}
+
+/// Allocates `size` bytes for OSQP, equivalent to `malloc`.
+///
+/// # Safety
+///
+/// The returned memory must only be freed using `osqp_rs_free`.
+#[no_mangle]
+pub unsafe extern "C" fn osqp_rs_malloc(size: usize) -> *mut c_void {
+    allocate(size, false)
+}
+
+/// Allocates `num * size` zeroed bytes for OSQP, equivalent to `calloc`.
+///
+/// # Safety
+///
+/// The returned memory must only be freed using `osqp_rs_free`.
+#[no_mangle]
+pub unsafe extern "C" fn osqp_rs_calloc(num: usize, size: usize) -> *mut c_void {
+    match num.checked_mul(size) {
+        Some(size) => allocate(size, true),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Resizes memory allocated for OSQP, equivalent to `realloc`.
+///
+/// # Safety
+///
+/// `ptr` must be null or have been returned by one of the allocation functions in this module
+/// and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn osqp_rs_realloc(ptr: *mut c_void, new_size: usize) -> *mut c_void {
+    if ptr.is_null() {
+        return allocate(new_size, false);
+    }
+    let header = header(ptr);
+    let Header { size, counter } = ptr::read(header);
+    let new_layout = match layout(new_size) {
+        Some(layout) => layout,
+        None => return ptr::null_mut(),
+    };
+    // `size` was accepted by `layout` when the memory was allocated
+    let old_layout = Layout::from_size_align_unchecked(size + HEADER_SIZE, HEADER_SIZE);
+    let base = alloc::realloc(header as *mut u8, old_layout, new_layout.size());
+    if base.is_null() {
+        return ptr::null_mut();
+    }
+
+    // The reallocated memory remains owned by the counter of the original allocation
+    if !counter.is_null() {
+        (*counter).fetch_add(new_size, Ordering::Relaxed);
+        (*counter).fetch_sub(size, Ordering::Relaxed);
+    }
+    ptr::write(
+        base as *mut Header,
+        Header {
+            size: new_size,
+            counter,
+        },
+    );
+    base.add(HEADER_SIZE) as *mut c_void
+}
+
+/// Frees memory allocated for OSQP, equivalent to `free`.
+///
+/// # Safety
+///
+/// `ptr` must be null or have been returned by one of the allocation functions in this module
+/// and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn osqp_rs_free(ptr: *mut c_void) {
+    if ptr.is_null() {
+        return;
+    }
+    let header = header(ptr);
+    let Header { size, counter } = ptr::read(header);
+    if !counter.is_null() {
+        (*counter).fetch_sub(size, Ordering::Relaxed);
+    }
+    alloc::dealloc(
+        header as *mut u8,
+        Layout::from_size_align_unchecked(size + HEADER_SIZE, HEADER_SIZE),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem;
+
+    #[test]
+    fn header_fits() {
+        assert!(mem::size_of::<Header>() <= HEADER_SIZE);
+        assert!(mem::align_of::<Header>() <= HEADER_SIZE);
+    }
+
+    #[test]
+    fn allocations_are_counted() {
+        let counter = AtomicUsize::new(0);
+        unsafe {
+            let (a, b) =
+                with_allocation_counter(&counter, || (osqp_rs_malloc(100), osqp_rs_calloc(10, 8)));
+            assert_eq!(counter.load(Ordering::Relaxed), 180);
+            assert!(std::slice::from_raw_parts(b as *const u8, 80)
+                .iter()
+                .all(|&v| v == 0));
+
+            // Allocations outside `with_allocation_counter` are not counted
+            let c = osqp_rs_malloc(50);
+            osqp_rs_free(c);
+
+            let a = osqp_rs_realloc(a, 300);
+            assert_eq!(counter.load(Ordering::Relaxed), 380);
+            osqp_rs_free(a);
+            osqp_rs_free(b);
+            osqp_rs_free(ptr::null_mut());
+            assert_eq!(counter.load(Ordering::Relaxed), 0);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Redirection of the output printed by OSQP.
+//!
+//! OSQP is built with a custom printing header (see `shim/printing.h`) which forwards everything
+//! it prints to this module. Output is split into lines which are passed to the sink set using
+//! `set_print_sink`. By default each line is emitted as a `log` record with the target `osqp`.
+
+use std::cell::RefCell;
+use std::os::raw::c_char;
+use std::slice;
+use std::sync::RwLock;
+
+/// The severity of a line printed by OSQP.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::manual_non_exhaustive)]
+pub enum PrintLevel {
+    /// Progress and summary output, printed when `verbose` is enabled.
+    Info,
+    /// An error message.
+    Error,
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+type Sink = Box<dyn Fn(PrintLevel, &str) + Send + Sync>;
+
+static SINK: RwLock<Option<Sink>> = RwLock::new(None);
+
+thread_local! {
+    static LINE: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Sets the function which receives each line printed by OSQP in this process.
+///
+/// Lines are passed without their trailing newline. The sink is called on the thread running
+/// the solver and must not call `set_print_sink` or `reset_print_sink`. The sink is not called
+/// when printing is disabled (see `PRINTING_ENABLED`) or when linking a system OSQP library
+/// using the `system` feature.
+pub fn set_print_sink<F: Fn(PrintLevel, &str) + Send + Sync + 'static>(sink: F) {
+    *SINK.write().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(sink));
+}
+
+/// Restores the default sink which emits each line as a `log` record with the target `osqp`.
+pub fn reset_print_sink() {
+    *SINK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+fn print_line(line: &str) {
+    let level = if line.starts_with("ERROR in ") {
+        PrintLevel::Error
+    } else {
+        PrintLevel::Info
+    };
+    match *SINK.read().unwrap_or_else(|e| e.into_inner()) {
+        Some(ref sink) => sink(level, line),
+        None => match level {
+            PrintLevel::Error => log::error!(target: "osqp", "{}", line),
+            _ => log::info!(target: "osqp", "{}", line),
+        },
+    }
+}
+
+/// Receives output formatted by the OSQP printing shim.
+///
+/// # Safety
+///
+/// `msg` must point to `len` bytes which are valid for reads.
+#[no_mangle]
+pub unsafe extern "C" fn osqp_rs_print(msg: *const c_char, len: usize) {
+    let msg = String::from_utf8_lossy(slice::from_raw_parts(msg as *const u8, len));
+    let _ = LINE.try_with(|line| {
+        let mut line = line.borrow_mut();
+        line.push_str(&msg);
+        while let Some(end) = line.find('\n') {
+            let rest = line.split_off(end + 1);
+            print_line(line.trim_end_matches(&['\r', '\n'][..]));
+            *line = rest;
+        }
+    });
+}
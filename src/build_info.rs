@@ -0,0 +1,82 @@
+use osqp_sys as ffi;
+use std::ffi::CStr;
+use std::ops::BitOr;
+
+/// Returns the version of the linked OSQP library.
+pub fn version() -> &'static str {
+    unsafe { CStr::from_ptr(ffi::osqp_version()) }
+        .to_str()
+        .unwrap_or("")
+}
+
+/// Returns the features compiled into the linked OSQP library.
+pub fn capabilities() -> Capabilities {
+    let bits = unsafe { ffi::osqp_capabilities() } as u32;
+    // OSQP does not report whether profiling is enabled so `osqp-sys` records it when built
+    if ffi::PROFILING_ENABLED {
+        Capabilities(bits) | Capabilities::PROFILING
+    } else {
+        Capabilities(bits)
+    }
+}
+
+/// A set of features of the OSQP library, returned by `capabilities`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Capabilities(u32);
+
+impl Capabilities {
+    /// The direct linear system solver, `LinsysSolver::Direct`, is available.
+    pub const DIRECT_SOLVER: Capabilities = Capabilities(ffi::OSQP_CAPABILITY_DIRECT_SOLVER);
+    /// The indirect linear system solver, `LinsysSolver::Indirect`, is available.
+    pub const INDIRECT_SOLVER: Capabilities = Capabilities(ffi::OSQP_CAPABILITY_INDIRECT_SOLVER);
+    /// Code generation using `Problem::codegen` is available.
+    pub const CODEGEN: Capabilities = Capabilities(ffi::OSQP_CAPABILITY_CODEGEN);
+    /// The matrices `P` and `A` can be updated after setup.
+    pub const UPDATE_MATRICES: Capabilities = Capabilities(ffi::OSQP_CAPABILITY_UPDATE_MATRICES);
+    /// Derivatives can be computed using `Problem::adjoint_derivative`.
+    pub const DERIVATIVES: Capabilities = Capabilities(ffi::OSQP_CAPABILITY_DERIVATIVES);
+    /// Timing information is recorded and the `time_limit` setting is enforced.
+    pub const PROFILING: Capabilities = Capabilities(1 << 16);
+
+    /// Returns an empty set of capabilities.
+    pub fn empty() -> Capabilities {
+        Capabilities(0)
+    }
+
+    /// Returns true if all the capabilities in `other` are also in `self`.
+    pub fn contains(self, other: Capabilities) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the underlying bits.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+}
+
+impl BitOr for Capabilities {
+    type Output = Capabilities;
+
+    fn bitor(self, other: Capabilities) -> Capabilities {
+        Capabilities(self.0 | other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_and_capabilities() {
+        assert!(version().starts_with("1."));
+
+        let caps = capabilities();
+        // A system OSQP library may have been built without these
+        #[cfg(not(feature = "system"))]
+        assert!(caps.contains(
+            Capabilities::DIRECT_SOLVER | Capabilities::CODEGEN | Capabilities::DERIVATIVES
+        ));
+        assert!(caps.contains(Capabilities::empty()));
+        assert!(!Capabilities::DIRECT_SOLVER.contains(caps | Capabilities::INDIRECT_SOLVER));
+    }
+}
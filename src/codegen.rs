@@ -0,0 +1,348 @@
+use osqp_sys as ffi;
+use std::error::Error;
+use std::ffi::CString;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::{Path, MAIN_SEPARATOR};
+
+use {OsqpError, Problem};
+
+/// Which problem data can be updated in the generated code.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(clippy::manual_non_exhaustive)]
+pub enum EmbeddedMode {
+    /// Only the vectors `q`, `l` and `u` can be updated.
+    Vectors,
+    /// The vectors `q`, `l` and `u` and the elements of the matrices `P` and `A` can be updated.
+    VectorsAndMatrices,
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// The floating point type used by the generated code.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(clippy::manual_non_exhaustive)]
+pub enum FloatType {
+    /// Single precision (`float`).
+    Single,
+    /// Double precision (`double`).
+    Double,
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// The options used when generating C code for a problem using `Problem::codegen`.
+#[derive(Clone, Debug)]
+pub struct CodegenOptions {
+    prefix: String,
+    defines: ffi::OSQPCodegenDefines,
+    emit_library: bool,
+}
+
+impl CodegenOptions {
+    /// Sets the string prepended to the names of the generated files and variables.
+    pub fn prefix(mut self, value: &str) -> CodegenOptions {
+        self.prefix = value.to_owned();
+        self
+    }
+
+    /// Sets which problem data can be updated in the generated code.
+    pub fn embedded_mode(mut self, value: EmbeddedMode) -> CodegenOptions {
+        self.defines.embedded_mode = match value {
+            EmbeddedMode::Vectors => 1,
+            EmbeddedMode::VectorsAndMatrices => 2,
+            EmbeddedMode::__Nonexhaustive => unreachable!(),
+        };
+        self
+    }
+
+    /// Sets the floating point type used by the generated code.
+    pub fn float_type(mut self, value: FloatType) -> CodegenOptions {
+        self.defines.float_type = match value {
+            FloatType::Single => 1,
+            FloatType::Double => 0,
+            FloatType::__Nonexhaustive => unreachable!(),
+        };
+        self
+    }
+
+    /// Enables printing in the generated code.
+    pub fn printing(mut self, value: bool) -> CodegenOptions {
+        self.defines.printing_enable = value as ffi::osqp_int;
+        self
+    }
+
+    /// Enables timing in the generated code.
+    pub fn profiling(mut self, value: bool) -> CodegenOptions {
+        self.defines.profiling_enable = value as ffi::osqp_int;
+        self
+    }
+
+    /// Enables copying the OSQP C sources required to build the generated code into the output
+    /// directory.
+    ///
+    /// The sources are copied from the `osqp-sys` build directory so this is only possible on
+    /// the machine which built the crate. The sources are not available when linking a system
+    /// OSQP library using the `system` feature.
+    pub fn emit_library(mut self, value: bool) -> CodegenOptions {
+        self.emit_library = value;
+        self
+    }
+}
+
+impl Default for CodegenOptions {
+    fn default() -> CodegenOptions {
+        unsafe {
+            let mut defines: ffi::OSQPCodegenDefines = mem::zeroed();
+            ffi::osqp_set_default_codegen_defines(&mut defines);
+            CodegenOptions {
+                prefix: String::new(),
+                defines,
+                emit_library: false,
+            }
+        }
+    }
+}
+
+impl Problem {
+    /// Generates C code for an allocation-free solver of this problem in `dir`.
+    ///
+    /// The generated workspace contains the problem data and settings currently used by the
+    /// solver. `dir` must already exist.
+    pub fn codegen<D: AsRef<Path>>(
+        &mut self,
+        dir: D,
+        options: CodegenOptions,
+    ) -> Result<(), CodegenError> {
+        let dir = dir.as_ref();
+        let mut dir_str = dir.to_str().ok_or(CodegenError::InvalidPath)?.to_owned();
+        // OSQP prepends the directory to the file names
+        if !dir_str.ends_with(MAIN_SEPARATOR) {
+            dir_str.push(MAIN_SEPARATOR);
+        }
+        let dir_c = CString::new(dir_str).map_err(|_| CodegenError::InvalidPath)?;
+        let prefix_c = CString::new(&*options.prefix).map_err(|_| CodegenError::InvalidPrefix)?;
+
+        if options.emit_library {
+            copy_sources(Path::new(ffi::OSQP_SOURCE_DIR), dir).map_err(CodegenError::Io)?;
+        }
+
+        let mut defines = options.defines;
+        unsafe {
+            let ret = self.with_memory(|| {
+                ffi::osqp_codegen(self.solver, dir_c.as_ptr(), prefix_c.as_ptr(), &mut defines)
+            });
+            if ret != 0 {
+                return Err(CodegenError::Osqp(OsqpError::from_ffi(ret)));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn copy_sources(src: &Path, dst: &Path) -> io::Result<()> {
+    if src.as_os_str().is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "OSQP sources are not available when linking a system OSQP library",
+        ));
+    }
+    for dir in &["include", "src", "algebra/_common", "algebra/builtin"] {
+        copy_dir(&src.join(dir), &dst.join(dir))?;
+    }
+    Ok(())
+}
+
+fn copy_dir(src: &Path, dst: &Path) -> io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            copy_dir(&path, &dst.join(entry.file_name()))?;
+        } else if entry.file_name() != "osqp_configure.h" {
+            // The configuration header is generated by `osqp_codegen` for the embedded build
+            fs::copy(&path, dst.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+/// An error that can occur when generating code using `Problem::codegen`.
+#[derive(Debug)]
+#[allow(clippy::manual_non_exhaustive)]
+pub enum CodegenError {
+    /// The output directory is not valid UTF-8 or contains a nul byte.
+    InvalidPath,
+    /// The file prefix contains a nul byte.
+    InvalidPrefix,
+    /// The OSQP sources could not be copied to the output directory.
+    Io(io::Error),
+    /// OSQP returned an error.
+    Osqp(OsqpError),
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodegenError::InvalidPath => {
+                "output directory is not valid UTF-8 or contains a nul byte".fmt(f)
+            }
+            CodegenError::InvalidPrefix => "file prefix contains a nul byte".fmt(f),
+            CodegenError::Io(err) => write!(f, "failed to copy OSQP sources: {}", err),
+            CodegenError::Osqp(err) => err.fmt(f),
+            CodegenError::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+impl Error for CodegenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CodegenError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tests::example_problem;
+    use Settings;
+
+    #[cfg(all(unix, not(feature = "system")))]
+    #[test]
+    fn codegen_solves() {
+        use float;
+        use std::fs;
+        use std::path::PathBuf;
+        use std::process::{self, Command};
+
+        const MAIN_C: &str = r#"
+#include <stdio.h>
+#include "osqp.h"
+#include "test_workspace.h"
+
+int main(void) {
+    if (osqp_solve(&test_solver) != 0) {
+        return 1;
+    }
+    printf("%.17g %.17g\n", (double)test_solver.solution->x[0],
+           (double)test_solver.solution->x[1]);
+    return 0;
+}
+"#;
+
+        fn c_files(dir: &Path, files: &mut Vec<PathBuf>, include_dirs: &mut Vec<PathBuf>) {
+            include_dirs.push(dir.to_owned());
+            for entry in fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    c_files(&path, files, include_dirs);
+                } else if path.extension().map_or(false, |ext| ext == "c") {
+                    files.push(path);
+                }
+            }
+        }
+
+        fn run(cmd: &mut Command) {
+            let status = cmd.status().expect("failed to run command");
+            assert!(status.success(), "{:?} failed", cmd);
+        }
+
+        let settings = Settings::default().verbose(false);
+        let mut prob = example_problem(&settings).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("osqp-codegen-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let float_type = if mem::size_of::<float>() == mem::size_of::<f32>() {
+            FloatType::Single
+        } else {
+            FloatType::Double
+        };
+        let options = CodegenOptions::default()
+            .prefix("test_")
+            .embedded_mode(EmbeddedMode::VectorsAndMatrices)
+            .float_type(float_type)
+            .printing(false)
+            .emit_library(true);
+        prob.codegen(&dir, options).unwrap();
+        let workspace = dir.join("test_workspace.c");
+        assert!(workspace.exists());
+        assert!(dir.join("test_workspace.h").exists());
+
+        // Build the emitted sources into a static library so the linker only pulls in the
+        // objects the embedded solver uses
+        let mut sources = Vec::new();
+        let mut include_dirs = Vec::new();
+        c_files(&dir, &mut sources, &mut include_dirs);
+        let objects = dir.join("objects");
+        fs::create_dir_all(&objects).unwrap();
+        let mut ar = Command::new("ar");
+        ar.arg("crs").arg(dir.join("libembedded.a"));
+        for (i, source) in sources.iter().filter(|&s| *s != workspace).enumerate() {
+            let object = objects.join(format!("{}.o", i));
+            let mut cc = Command::new("cc");
+            cc.arg("-c").arg(source).arg("-o").arg(&object);
+            for include in &include_dirs {
+                cc.arg("-I").arg(include);
+            }
+            run(&mut cc);
+            ar.arg(object);
+        }
+        run(&mut ar);
+
+        let main = dir.join("main.c");
+        fs::write(&main, MAIN_C).unwrap();
+        let exe = dir.join("embedded");
+        let mut cc = Command::new("cc");
+        cc.arg(&main).arg(&workspace);
+        for include in &include_dirs {
+            cc.arg("-I").arg(include);
+        }
+        cc.arg(dir.join("libembedded.a"))
+            .arg("-lm")
+            .arg("-o")
+            .arg(&exe);
+        run(&mut cc);
+
+        let output = Command::new(&exe).output().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(output.status.success());
+        let x = String::from_utf8(output.stdout)
+            .unwrap()
+            .split_whitespace()
+            .map(|v| v.parse::<float>().unwrap())
+            .collect::<Vec<_>>();
+
+        let expected = prob.solve().unwrap().x().unwrap().to_vec();
+        assert_eq!(x.len(), expected.len());
+        // The embedded solver is built without polishing so only compare to solver accuracy
+        for (x, expected) in x.iter().zip(&expected) {
+            assert!((x - expected).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn codegen_invalid_defines() {
+        let settings = Settings::default().verbose(false);
+        let mut prob = example_problem(&settings).unwrap();
+
+        let mut options = CodegenOptions::default();
+        options.defines.embedded_mode = 3;
+        match prob.codegen(std::env::temp_dir(), options) {
+            Err(CodegenError::Osqp(OsqpError::CodegenDefinesInvalid)) => (),
+            other => panic!("expected CodegenDefinesInvalid but found {:?}", other),
+        }
+    }
+}
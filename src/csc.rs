@@ -1,6 +1,9 @@
 use osqp_sys as ffi;
 use std::borrow::Cow;
+use std::convert::TryFrom;
 use std::iter;
+use std::marker::PhantomData;
+use std::mem;
 use std::slice;
 
 use float;
@@ -174,6 +177,21 @@ impl<'a> CscMatrix<'a> {
         true
     }
 
+    /// Returns the index into `data` of the element at (`row`, `col`).
+    ///
+    /// Returns `None` if the element is empty, i.e. not present in the sparse encoding of the
+    /// matrix, or lies outside the matrix. The row indices within each column must be sorted, as
+    /// is the case for any matrix accepted by `Problem::new`.
+    pub fn data_index(&self, row: usize, col: usize) -> Option<usize> {
+        if row >= self.nrows || col >= self.ncols {
+            return None;
+        }
+        let col_start_idx = *self.indptr.get(col)?;
+        let col_end_idx = *self.indptr.get(col + 1)?;
+        let rows = self.indices.get(col_start_idx..col_end_idx)?;
+        rows.binary_search(&row).ok().map(|i| col_start_idx + i)
+    }
+
     /// Extracts the upper triangular elements of the matrix.
     ///
     /// This operation performs no allocations if the matrix is already structurally upper
@@ -266,25 +284,25 @@ impl<'a> CscMatrix<'a> {
         }
     }
 
-    // TODO: Remove *mut in return type here
-    pub(crate) unsafe fn to_ffi(&self) -> *mut OSQPCscMatrix {
-        // Casting is safe as at this point no indices exceed isize::MAX and osqp_int is a signed
-        // integer of the same size as usize/isize.
-        ffi::OSQPCscMatrix_new(
-            self.nrows as ffi::osqp_int,
-            self.ncols as ffi::osqp_int,
-            self.data.len() as ffi::osqp_int,
-            self.data.as_ptr() as *mut float,
-            self.indices.as_ptr() as *mut usize as *mut ffi::osqp_int,
-            self.indptr.as_ptr() as *mut usize as *mut ffi::osqp_int,
-        )
+    /// Returns a view of this matrix for passing to OSQP.
+    ///
+    /// Returns `None` if the dimensions or the number of elements do not fit in `osqp_int`. The
+    /// matrix must be a valid CSC matrix.
+    pub(crate) fn to_ffi(&self) -> Option<FfiCscMatrix<'_>> {
+        FfiCscMatrix::new(self, self.data.as_ptr() as *mut float)
+    }
+
+    /// Like `to_ffi` but allows OSQP to write to the matrix elements.
+    pub(crate) fn to_ffi_mut(&mut self) -> Option<FfiCscMatrix<'_>> {
+        let data = self.data.to_mut().as_mut_ptr();
+        FfiCscMatrix::new(self, data)
     }
 
     #[allow(dead_code)]
     pub(crate) unsafe fn from_ffi<'b>(csc: *const ffi::OSQPCscMatrix) -> CscMatrix<'b> {
         let nrows = (*csc).m as usize;
         let ncols = (*csc).n as usize;
-        let indptr = Cow::Borrowed(slice::from_raw_parts((*csc).p as *const usize, ncols + 1));
+        let indptr = indices_from_ffi((*csc).p, ncols + 1);
         // OSQP sets `nzmax = max(nnz, 1)`, presumably so as not to have zero length allocations.
         let nnz = if indptr[ncols] == 0 {
             0
@@ -296,17 +314,58 @@ impl<'a> CscMatrix<'a> {
             nrows,
             ncols,
             indptr,
-            indices: Cow::Borrowed(slice::from_raw_parts((*csc).i as *const usize, nnz)),
+            indices: indices_from_ffi((*csc).i, nnz),
             data: Cow::Borrowed(slice::from_raw_parts((*csc).x as *const float, nnz)),
         }
     }
 
-    pub(crate) fn assert_same_sparsity_structure(&self, other: &CscMatrix) {
-        assert_eq!(self.nrows, other.nrows);
-        assert_eq!(self.ncols, other.ncols);
-        assert_eq!(&*self.indptr, &*other.indptr);
-        assert_eq!(&*self.indices, &*other.indices);
-        assert_eq!(self.data.len(), other.data.len());
+    /// Checks that `other` has the same shape and sparsity structure as this matrix.
+    ///
+    /// The number of elements in the data arrays is not compared.
+    pub(crate) fn check_same_sparsity_structure(
+        &self,
+        other: &CscMatrix,
+    ) -> Result<(), StructureMismatch> {
+        if self.nrows != other.nrows
+            || self.ncols != other.ncols
+            || self.indptr.len() != other.indptr.len()
+        {
+            return Err(StructureMismatch::Shape);
+        }
+
+        for col in 0..self.indptr.len().saturating_sub(1) {
+            let rows = self.indices.get(self.indptr[col]..self.indptr[col + 1]);
+            // `other` has not necessarily been validated so must be indexed with care
+            let other_rows = other.indices.get(other.indptr[col]..other.indptr[col + 1]);
+            if rows != other_rows {
+                return Err(StructureMismatch::Column(col));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn into_owned(self) -> CscMatrix<'static> {
+        CscMatrix {
+            nrows: self.nrows,
+            ncols: self.ncols,
+            indptr: Cow::Owned(self.indptr.into_owned()),
+            indices: Cow::Owned(self.indices.into_owned()),
+            data: Cow::Owned(self.data.into_owned()),
+        }
+    }
+
+    /// Computes the matrix-vector product `self * x`.
+    pub(crate) fn mul_vec(&self, x: &[float]) -> Vec<float> {
+        assert_eq!(x.len(), self.ncols);
+        let mut out = vec![0.0; self.nrows];
+        for (col, &x_col) in x.iter().enumerate() {
+            let range = self.indptr[col]..self.indptr[col + 1];
+            for (&row, &value) in self.indices[range.clone()].iter().zip(&self.data[range]) {
+                out[row] += value * x_col;
+            }
+        }
+        out
     }
 
     pub(crate) fn is_valid(&self) -> bool {
@@ -348,6 +407,94 @@ impl<'a> CscMatrix<'a> {
     }
 }
 
+/// A CSC matrix allocated by OSQP which refers to the arrays of a `CscMatrix`.
+///
+/// The wrapper is freed when dropped. Index arrays are converted if `osqp_int` and `usize` have
+/// different sizes.
+pub(crate) struct FfiCscMatrix<'a> {
+    ptr: *mut OSQPCscMatrix,
+    _indptr: Cow<'a, [ffi::osqp_int]>,
+    _indices: Cow<'a, [ffi::osqp_int]>,
+    _data: PhantomData<&'a mut [float]>,
+}
+
+impl<'a> FfiCscMatrix<'a> {
+    fn new(matrix: &'a CscMatrix, data: *mut float) -> Option<FfiCscMatrix<'a>> {
+        // Every index is at most the number of rows or elements so it also fits if these fit
+        let nrows = ffi::osqp_int::try_from(matrix.nrows).ok()?;
+        let ncols = ffi::osqp_int::try_from(matrix.ncols).ok()?;
+        let nnz = ffi::osqp_int::try_from(matrix.data.len()).ok()?;
+        let indptr = ffi_indices(&matrix.indptr);
+        let indices = ffi_indices(&matrix.indices);
+        unsafe {
+            let ptr = ffi::OSQPCscMatrix_new(
+                nrows,
+                ncols,
+                nnz,
+                data,
+                indices.as_ptr() as *mut ffi::osqp_int,
+                indptr.as_ptr() as *mut ffi::osqp_int,
+            );
+            if ptr.is_null() {
+                return None;
+            }
+            Some(FfiCscMatrix {
+                ptr,
+                _indptr: indptr,
+                _indices: indices,
+                _data: PhantomData,
+            })
+        }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut OSQPCscMatrix {
+        self.ptr
+    }
+}
+
+impl<'a> Drop for FfiCscMatrix<'a> {
+    fn drop(&mut self) {
+        unsafe { ffi::OSQPCscMatrix_free(self.ptr) };
+    }
+}
+
+/// Converts indices to `osqp_int`, reinterpreting the slice if the types have the same size.
+///
+/// Each index must fit in `osqp_int`.
+pub(crate) fn ffi_indices(indices: &[usize]) -> Cow<'_, [ffi::osqp_int]> {
+    if mem::size_of::<ffi::osqp_int>() == mem::size_of::<usize>() {
+        // Reinterpreting is safe as osqp_int is a signed integer of the same size as usize and
+        // each index fits in it
+        unsafe {
+            Cow::Borrowed(slice::from_raw_parts(
+                indices.as_ptr() as *const ffi::osqp_int,
+                indices.len(),
+            ))
+        }
+    } else {
+        Cow::Owned(indices.iter().map(|&i| i as ffi::osqp_int).collect())
+    }
+}
+
+unsafe fn indices_from_ffi<'b>(ptr: *const ffi::osqp_int, len: usize) -> Cow<'b, [usize]> {
+    if mem::size_of::<ffi::osqp_int>() == mem::size_of::<usize>() {
+        Cow::Borrowed(slice::from_raw_parts(ptr as *const usize, len))
+    } else {
+        let indices = slice::from_raw_parts(ptr, len);
+        Cow::Owned(indices.iter().map(|&i| i as usize).collect())
+    }
+}
+
+/// The way in which the sparsity structure of a matrix differs from an expected structure.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum StructureMismatch {
+    /// The matrices have a different number of rows or columns.
+    Shape,
+    /// The row indices of the non-zero elements differ, the first difference being in this
+    /// column.
+    Column(usize),
+}
+
 // Any &CscMatrix can be converted into a CscMatrix without allocation due to the use of Cow.
 impl<'a, 'b: 'a> From<&'a CscMatrix<'b>> for CscMatrix<'a> {
     fn from(mat: &'a CscMatrix<'b>) -> CscMatrix<'a> {
@@ -519,15 +666,72 @@ mod tests {
     fn same_sparsity_structure_ok() {
         let mat1: CscMatrix = (&[[1.0, 2.0, 0.0], [3.0, 0.0, 0.0], [0.0, 5.0, 0.0]]).into();
         let mat2: CscMatrix = (&[[7.0, 8.0, 0.0], [9.0, 0.0, 0.0], [0.0, 10.0, 0.0]]).into();
-        mat1.assert_same_sparsity_structure(&mat2);
+        assert_eq!(mat1.check_same_sparsity_structure(&mat2), Ok(()));
     }
 
     #[test]
-    #[should_panic]
-    fn different_sparsity_structure_panics() {
+    fn different_sparsity_structure() {
         let mat1: CscMatrix = (&[[1.0, 2.0, 0.0], [3.0, 0.0, 0.0], [0.0, 5.0, 6.0]]).into();
         let mat2: CscMatrix = (&[[7.0, 8.0, 0.0], [9.0, 0.0, 0.0], [0.0, 10.0, 0.0]]).into();
-        mat1.assert_same_sparsity_structure(&mat2);
+        assert_eq!(
+            mat1.check_same_sparsity_structure(&mat2),
+            Err(StructureMismatch::Column(2))
+        );
+
+        // Same number of non-zero elements but in different rows
+        let mat3: CscMatrix = (&[[1.0, 2.0, 0.0], [0.0, 0.0, 0.0], [3.0, 5.0, 6.0]]).into();
+        assert_eq!(
+            mat1.check_same_sparsity_structure(&mat3),
+            Err(StructureMismatch::Column(0))
+        );
+
+        let mat4: CscMatrix = (&[[1.0, 2.0], [3.0, 0.0], [0.0, 5.0]]).into();
+        assert_eq!(
+            mat1.check_same_sparsity_structure(&mat4),
+            Err(StructureMismatch::Shape)
+        );
+    }
+
+    #[test]
+    fn data_index() {
+        let mat: CscMatrix = (&[[1.0, 2.0, 0.0], [3.0, 0.0, 0.0], [0.0, 5.0, 6.0]]).into();
+        assert_eq!(mat.data_index(0, 0), Some(0));
+        assert_eq!(mat.data_index(1, 0), Some(1));
+        assert_eq!(mat.data_index(2, 1), Some(3));
+        assert_eq!(mat.data_index(2, 2), Some(4));
+        assert_eq!(mat.data_index(1, 1), None);
+        assert_eq!(mat.data_index(3, 0), None);
+        assert_eq!(mat.data_index(0, 3), None);
+    }
+
+    #[test]
+    fn mul_vec() {
+        let mat: CscMatrix = (&[[1.0, 2.0, 0.0], [3.0, 0.0, 0.0], [0.0, 5.0, 6.0]]).into();
+        assert_eq!(mat.mul_vec(&[1.0, 2.0, 3.0]), vec![5.0, 3.0, 28.0]);
+
+        let empty = CscMatrix::from_column_iter_dense(0, 2, iter::empty());
+        assert_eq!(empty.mul_vec(&[1.0, 2.0]), Vec::<float>::new());
+    }
+
+    #[test]
+    fn ffi_round_trip() {
+        let mat = &[[1.0, 2.0], [3.0, 0.0], [0.0, 4.0]];
+        let csc: CscMatrix = mat.into();
+        let csc_ffi = csc.to_ffi().unwrap();
+        let round_trip = unsafe { CscMatrix::from_ffi(csc_ffi.as_ptr()) };
+        assert_eq!(csc, round_trip);
+
+        // Indices are only copied if `osqp_int` and `usize` have different sizes
+        let indices = ffi_indices(&csc.indices);
+        assert_eq!(&*indices, &[0, 1, 0, 2]);
+        let borrowed = match indices {
+            Cow::Borrowed(_) => true,
+            Cow::Owned(_) => false,
+        };
+        assert_eq!(
+            borrowed,
+            mem::size_of::<ffi::osqp_int>() == mem::size_of::<usize>()
+        );
     }
 
     #[test]
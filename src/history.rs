@@ -0,0 +1,226 @@
+use std::io::{self, Write};
+
+use {AdaptiveRho, Problem, Progress, Settings, SettingsError, SolveError, StepError, UpdateError};
+
+impl Problem {
+    /// Enables recording a `ConvergenceHistory` during each call to `Problem::solve`.
+    ///
+    /// The history is returned by `Status::history`. While recording, the problem is solved
+    /// using `Problem::solve_steps` with steps chosen so that OSQP checks termination and adapts
+    /// rho at the same iterations as a solve which does not record its history. A snapshot is
+    /// recorded every `check_termination` iterations or, if rho is adapted, every common
+    /// multiple of `check_termination` and `adaptive_rho_interval`. If termination checking is
+    /// disabled only the final state is recorded.
+    ///
+    /// The iterations, rho updates and solve, polish and run times reported by the status are
+    /// totals over all steps, while the setup and update times are those reported by the first
+    /// step. Timings still differ slightly from an unrecorded solve as each step is a separate
+    /// OSQP solve.
+    ///
+    /// Solving returns `SolveError::HistorySettings` if rho is adapted based on time or the KKT
+    /// error, as those adaptations cannot be reproduced while stepping.
+    pub fn record_history(&mut self, enabled: bool) {
+        self.record_history = enabled;
+    }
+
+    pub(crate) fn solve_recording_history(&mut self) -> Result<(), SolveError> {
+        let settings = Settings::from_problem(self);
+        let chunk = history_chunk(&settings)
+            .map_err(|err| SolveError::HistorySettings(UpdateError::InvalidSetting(err)))?;
+
+        let mut entries = Vec::new();
+        {
+            let mut steps = self.solve_steps(chunk);
+            for progress in steps.by_ref() {
+                entries.push(progress.map_err(|err| match err {
+                    StepError::Update(err) => SolveError::HistorySettings(err),
+                    StepError::Solve(err) => err,
+                    StepError::__Nonexhaustive => unreachable!(),
+                })?);
+            }
+            steps.finish();
+        }
+        self.history = Some(ConvergenceHistory { entries });
+        Ok(())
+    }
+}
+
+/// Returns the number of iterations per step for which stepping performs the same iterations as
+/// a single solve.
+///
+/// OSQP counts iterations from the start of each step so termination checks and rho updates
+/// only happen at the same iterations if the step length is a multiple of their intervals.
+fn history_chunk(settings: &Settings) -> Result<u32, SettingsError> {
+    let check_termination = match settings.get_check_termination() {
+        Some(interval) => interval,
+        None => return Ok(settings.get_max_iter()),
+    };
+    match settings.get_adaptive_rho() {
+        AdaptiveRho::Disabled => Ok(check_termination),
+        AdaptiveRho::Iterations => match settings.get_adaptive_rho_interval() {
+            Some(interval) => Ok(lcm(check_termination, interval)),
+            None => Ok(check_termination),
+        },
+        _ => Err(SettingsError {
+            field: "adaptive_rho",
+            value: format!("{:?}", settings.inner.adaptive_rho),
+            reason: "must be disabled or based on iterations while recording history",
+        }),
+    }
+}
+
+fn lcm(a: u32, b: u32) -> u32 {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        let r = x % y;
+        x = y;
+        y = r;
+    }
+    a / x * b
+}
+
+/// The state of the solver recorded at each termination check of a solve.
+///
+/// Recording is enabled using `Problem::record_history`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConvergenceHistory {
+    entries: Vec<Progress>,
+}
+
+impl ConvergenceHistory {
+    /// Returns the recorded snapshots in the order they were taken.
+    pub fn entries(&self) -> &[Progress] {
+        &self.entries
+    }
+
+    /// Writes the history as CSV with a header row.
+    ///
+    /// The columns are `iter`, `obj_val`, `pri_res`, `dua_res`, `rho` and `elapsed`, the time
+    /// since the start of the solve in seconds.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "iter,obj_val,pri_res,dua_res,rho,elapsed")?;
+        for entry in &self.entries {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{}",
+                entry.iter(),
+                entry.obj_val(),
+                entry.pri_res(),
+                entry.dua_res(),
+                entry.rho(),
+                entry.elapsed().as_secs_f64()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns the history as CSV. See `ConvergenceHistory::write_csv`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = Vec::new();
+        self.write_csv(&mut csv)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(csv).expect("CSV is valid UTF-8")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float;
+    use tests::example_problem;
+    use OwnedStatus;
+
+    #[test]
+    fn convergence_history() {
+        let settings = Settings::default()
+            .verbose(false)
+            .adaptive_rho(false)
+            .check_termination(Some(5));
+        let mut prob = example_problem(&settings).unwrap();
+        assert!(prob.solve().unwrap().history().is_none());
+
+        prob.record_history(true);
+        let status = prob.solve_owned().unwrap();
+        let history = status.history().unwrap();
+        let entries = history.entries();
+        assert!(!entries.is_empty());
+        assert!(entries.iter().all(|entry| entry.iter() % 5 == 0));
+        assert_eq!(entries.last().unwrap().iter(), status.info().iter());
+        assert!(entries.windows(2).all(|e| e[0].elapsed() <= e[1].elapsed()));
+
+        let csv = history.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("iter,obj_val,pri_res,dua_res,rho,elapsed")
+        );
+        assert_eq!(lines.count(), entries.len());
+        let first = csv.lines().nth(1).unwrap().split(',').collect::<Vec<_>>();
+        assert_eq!(first[0], "5");
+        assert_eq!(first[1].parse::<float>().unwrap(), entries[0].obj_val());
+
+        prob.record_history(false);
+        assert!(prob.solve().unwrap().history().is_none());
+    }
+
+    #[test]
+    fn history_does_not_change_solve() {
+        let settings = Settings::default().verbose(false);
+        let mut unrecorded = example_problem(&settings).unwrap();
+        let expected = unrecorded.solve_owned().unwrap();
+
+        let mut prob = example_problem(&settings).unwrap();
+        prob.record_history(true);
+        let status = prob.solve_owned().unwrap();
+        let settings = Settings::from_problem(&prob);
+        let chunk = lcm(
+            settings.get_check_termination().unwrap(),
+            settings.get_adaptive_rho_interval().unwrap(),
+        );
+        let entries = status.history().unwrap().entries();
+        assert!(entries
+            .iter()
+            .all(|entry| entry.iter() % chunk == 0 || entry.iter() == status.info().iter()));
+
+        // Timings differ between solves so only the deterministic information is compared
+        let (info, expected_info) = (status.info(), expected.info());
+        assert_eq!(info.iter(), expected_info.iter());
+        assert_eq!(info.rho_updates(), expected_info.rho_updates());
+        assert_eq!(info.rho_estimate(), expected_info.rho_estimate());
+        assert_eq!(info.status_val(), expected_info.status_val());
+        assert_eq!(info.polish_status(), expected_info.polish_status());
+        assert_eq!(info.obj_val(), expected_info.obj_val());
+        assert_eq!(info.pri_res(), expected_info.pri_res());
+        assert_eq!(info.dua_res(), expected_info.dua_res());
+        assert_eq!(
+            info.run_time().is_some(),
+            expected_info.run_time().is_some()
+        );
+        assert_eq!(
+            settings.get_rho(),
+            Settings::from_problem(&unrecorded).get_rho()
+        );
+        match (status, expected) {
+            (OwnedStatus::Solved(solution), OwnedStatus::Solved(expected)) => {
+                assert_eq!(solution.x(), expected.x());
+                assert_eq!(solution.y(), expected.y());
+            }
+            _ => panic!("expected Solved"),
+        }
+    }
+
+    #[test]
+    fn history_rejects_time_based_rho() {
+        let settings = Settings::default()
+            .verbose(false)
+            .adaptive_rho(AdaptiveRho::Time);
+        let mut prob = example_problem(&settings).unwrap();
+        prob.record_history(true);
+        match prob.solve() {
+            Err(SolveError::HistorySettings(UpdateError::InvalidSetting(err))) => {
+                assert_eq!(err.field, "adaptive_rho")
+            }
+            other => panic!("expected HistorySettings but found {:?}", other),
+        }
+    }
+}
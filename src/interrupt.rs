@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A handle used to cancel a solve running on another thread.
+///
+/// Handles are created using `Problem::interrupt_handle`.
+#[derive(Clone, Debug)]
+pub struct InterruptHandle {
+    pub(crate) flag: Arc<AtomicBool>,
+}
+
+impl InterruptHandle {
+    /// Interrupts the solve currently running on the associated `Problem`.
+    ///
+    /// The solve terminates at the end of its current ADMM iteration and `Problem::solve`
+    /// returns `Status::Interrupted`. A `SolveSteps` iterator cancelled between steps stops
+    /// before the next step. Calling this method when no solve is running has no effect.
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use tests::example_problem;
+    use {Settings, Status};
+
+    #[test]
+    fn interrupt_solve() {
+        // Without termination checks the solver runs until it reaches max_iter
+        let settings = Settings::default()
+            .verbose(false)
+            .polishing(false)
+            .check_termination(None)
+            .max_iter(i32::MAX as u32);
+        let mut prob = example_problem(&settings).unwrap();
+
+        let handle = prob.interrupt_handle();
+        let canceller = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            handle.cancel();
+        });
+
+        match prob.solve().unwrap() {
+            Status::Interrupted(solution) => assert_eq!(solution.x().len(), 2),
+            other => panic!("unexpected status {:?}", other),
+        }
+        canceller.join().unwrap();
+    }
+
+    #[test]
+    fn interrupt_between_steps() {
+        let settings = Settings::default()
+            .verbose(false)
+            .check_termination(None)
+            .max_iter(100);
+        let mut prob = example_problem(&settings).unwrap();
+
+        // A cancellation made before stepping starts is ignored
+        let handle = prob.interrupt_handle();
+        handle.cancel();
+        let mut steps = prob.solve_steps(10);
+        assert_eq!(steps.next().unwrap().unwrap().iter(), 10);
+
+        handle.cancel();
+        assert!(steps.next().is_none());
+        assert_eq!(steps.iter(), 10);
+        match steps.finish() {
+            Status::Interrupted(solution) => assert_eq!(solution.x().len(), 2),
+            other => panic!("unexpected status {:?}", other),
+        }
+
+        // The next solve is not interrupted
+        if let Status::Interrupted(_) = prob.solve().unwrap() {
+            panic!("solve was interrupted");
+        }
+    }
+}
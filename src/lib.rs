@@ -54,7 +54,7 @@
 //! let mut prob = Problem::new(P, q, A, l, u, &settings).expect("failed to setup problem");
 //!
 //! // Solve problem
-//! let result = prob.solve();
+//! let result = prob.solve().expect("failed to run solver");
 //!
 //! // Print the solution
 //! println!("{:?}", result.x().expect("failed to solve problem"));
@@ -63,7 +63,8 @@
 //! # let expected = &[0.30137570387082474, 0.6983956863817343];
 //! # let x = result.solution().unwrap().x();
 //! # assert_eq!(expected.len(), x.len());
-//! # assert!(expected.iter().zip(x).all(|(&a, &b)| (a - b).abs() < 1e-9));
+//! # let tol = if cfg!(feature = "f32") { 1e-4 } else { 1e-9 };
+//! # assert!(expected.iter().zip(x).all(|(&a, &b)| (a - b).abs() < tol));
 //! ```
 
 extern crate osqp_sys;
@@ -72,47 +73,102 @@ use osqp_sys as ffi;
 use std::error::Error;
 use std::fmt;
 use std::ptr;
+#[cfg(feature = "interrupt")]
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "interrupt")]
+use std::sync::Arc;
+
+pub use osqp_sys::printing::{reset_print_sink, set_print_sink, PrintLevel};
+
+mod build_info;
+pub use build_info::{capabilities, version, Capabilities};
+
+mod codegen;
+pub use codegen::{CodegenError, CodegenOptions, EmbeddedMode, FloatType};
 
 mod csc;
 pub use csc::CscMatrix;
+use csc::{ffi_indices, StructureMismatch};
+
+mod history;
+pub use history::ConvergenceHistory;
+
+#[cfg(feature = "interrupt")]
+mod interrupt;
+#[cfg(feature = "interrupt")]
+pub use interrupt::InterruptHandle;
 
 mod settings;
-pub use settings::{LinsysSolver, Settings};
+pub use settings::{AdaptiveRho, LinsysSolver, Preconditioner, Settings, SettingsError};
 
 mod status;
+use status::solution_vec;
 
 pub use status::{
-    DualInfeasibilityCertificate, Failure, PolishStatus, PrimalInfeasibilityCertificate, Solution,
-    Status,
+    DualInfeasibilityCertificate, Failure, OwnedDualInfeasibilityCertificate, OwnedFailure,
+    OwnedPrimalInfeasibilityCertificate, OwnedSolution, OwnedStatus, PolishStatus,
+    PrimalInfeasibilityCertificate, Solution, SolutionQuality, SolveInfo, Status,
 };
 
+mod steps;
+pub use steps::{Progress, SolveSteps, StepError};
+
 #[allow(non_camel_case_types)]
-type float = f64;
+type float = ffi::osqp_float;
 
-// Ensure osqp_int is the same size as usize/isize.
-#[allow(dead_code)]
-fn assert_osqp_int_size() {
-    let _osqp_int_must_be_usize = ::std::mem::transmute::<ffi::osqp_int, usize>;
+macro_rules! check {
+    ($err:ident, $ret:expr) => {
+        let ret = $ret;
+        if ret != 0 {
+            return Err($err::Osqp(OsqpError::from_ffi(ret)));
+        }
+    };
 }
 
-macro_rules! check {
-    ($fun:ident, $ret:expr) => {
-        assert!(
-            $ret == 0,
-            "osqp_{} failed with exit code {}",
-            stringify!($fun),
-            $ret
-        );
+macro_rules! check_len {
+    ($name:expr, $expected:expr, $found:expr) => {
+        let (expected, found) = ($expected, $found);
+        if expected != found {
+            return Err(UpdateError::DimensionMismatch {
+                name: $name,
+                expected,
+                found,
+            });
+        }
     };
 }
 
 /// An instance of the OSQP solver.
+#[allow(non_snake_case)]
 pub struct Problem {
     solver: *mut ffi::OSQPSolver,
     /// Number of variables
     n: usize,
     /// Number of constraints
     m: usize,
+    /// Copy of the problem data currently used by the solver
+    P: CscMatrix<'static>,
+    q: Vec<float>,
+    A: CscMatrix<'static>,
+    l: Vec<float>,
+    u: Vec<float>,
+    /// Number of bytes currently allocated by OSQP for this solver
+    memory: Box<AtomicUsize>,
+    /// Whether `solve` records a `ConvergenceHistory`
+    record_history: bool,
+    /// The history recorded by the most recent solve
+    history: Option<ConvergenceHistory>,
+    /// Combined information of the earlier steps of `solve_steps`, which OSQP does not keep
+    steps_info: Option<SolveInfo>,
+    /// Whether `solve_steps` was interrupted between steps, which OSQP does not report
+    steps_interrupted: bool,
+    /// Primal and dual variables set by `warm_start` or `cold_start` since the last solve
+    warm_start_x: Option<Vec<float>>,
+    warm_start_y: Option<Vec<float>>,
+    /// Set by an `InterruptHandle` to cancel the running solve
+    #[cfg(feature = "interrupt")]
+    interrupt: Arc<AtomicBool>,
 }
 
 impl Problem {
@@ -146,6 +202,17 @@ impl Problem {
     ) -> Result<Problem, SetupError> {
         let invalid_data = |msg| Err(SetupError::DataInvalid(msg));
 
+        settings.validate().map_err(SetupError::InvalidSetting)?;
+
+        let linsys_solver = settings.get_linsys_solver();
+        let linsys_capability = match linsys_solver {
+            LinsysSolver::Indirect => Capabilities::INDIRECT_SOLVER,
+            _ => Capabilities::DIRECT_SOLVER,
+        };
+        if !capabilities().contains(linsys_capability) {
+            return Err(SetupError::LinsysSolverUnavailable(linsys_solver));
+        }
+
         unsafe {
             // Ensure the provided data is valid. While OSQP internally performs some validity
             // checks it can be made to read outside the provided buffers so all the invariants
@@ -186,16 +253,58 @@ impl Problem {
                 return invalid_data("P must be structurally upper triangular");
             }
 
-            // Calling `to_ffi` is safe as we have ensured that `P` and `A` are valid CSC matrices.
-            let P_ffi = P.to_ffi();
-            let A_ffi = A.to_ffi();
-
             let settings = &settings.inner as *const ffi::OSQPSettings as *mut ffi::OSQPSettings;
             let mut solver: *mut ffi::OSQPSolver = ptr::null_mut();
-
-            let status = ffi::osqp_setup(&mut solver, P_ffi, q.as_ptr(), A_ffi, l.as_ptr(), u.as_ptr(), m as ffi::osqp_int, n as ffi::osqp_int, settings);
+            let memory = Box::new(AtomicUsize::new(0));
+
+            let status = {
+                // Calling `to_ffi` is safe as we have ensured that `P` and `A` are valid CSC
+                // matrices. OSQP copies the matrices so they are freed once setup is complete.
+                let P_ffi = match P.to_ffi() {
+                    Some(P_ffi) => P_ffi,
+                    None => return invalid_data("P is too large for the OSQP integer type"),
+                };
+                let A_ffi = match A.to_ffi() {
+                    Some(A_ffi) => A_ffi,
+                    None => return invalid_data("A is too large for the OSQP integer type"),
+                };
+                ffi::memory::with_allocation_counter(&memory, || {
+                    ffi::osqp_setup(
+                        &mut solver,
+                        P_ffi.as_ptr(),
+                        q.as_ptr(),
+                        A_ffi.as_ptr(),
+                        l.as_ptr(),
+                        u.as_ptr(),
+                        m as ffi::osqp_int,
+                        n as ffi::osqp_int,
+                        settings,
+                    )
+                })
+            };
             let err = match status as ffi::osqp_error_type {
-                0 => return Ok(Problem { solver, n, m }),
+                0 => {
+                    return Ok(Problem {
+                        solver,
+                        n,
+                        m,
+                        P: P.into_owned(),
+                        q: q.to_vec(),
+                        A: A.into_owned(),
+                        l: l.to_vec(),
+                        u: u.to_vec(),
+                        memory,
+                        record_history: false,
+                        history: None,
+                        steps_info: None,
+                        steps_interrupted: false,
+                        // OSQP starts from zero after setup
+                        warm_start_x: Some(vec![0.0; n]),
+                        warm_start_y: Some(vec![0.0; m]),
+                        #[cfg(feature = "interrupt")]
+                        interrupt: Arc::new(AtomicBool::new(false)),
+                    });
+                }
                 ffi::OSQP_DATA_VALIDATION_ERROR => SetupError::DataInvalid(""),
                 ffi::OSQP_SETTINGS_VALIDATION_ERROR => SetupError::SettingsInvalid,
                 ffi::OSQP_ALGEBRA_LOAD_ERROR => SetupError::LinsysSolverLoadFailed,
@@ -215,117 +324,175 @@ impl Problem {
 
     /// Sets the linear part of the cost function to `q`.
     ///
-    /// Panics if the length of `q` is not the same as the number of problem variables.
-    pub fn update_lin_cost(&mut self, q: &[float]) {
+    /// Returns an error if the length of `q` is not the same as the number of problem variables.
+    pub fn update_lin_cost(&mut self, q: &[float]) -> Result<(), UpdateError> {
+        check_len!("q", self.n, q.len());
         unsafe {
-            assert_eq!(self.n, q.len());
             check!(
-                update_lin_cost,
+                UpdateError,
                 ffi::osqp_update_data_vec(self.solver, q.as_ptr(), ptr::null(), ptr::null())
             );
         }
+        self.q.copy_from_slice(q);
+        Ok(())
     }
 
     /// Sets the lower and upper bounds of the constraints to `l` and `u`.
     ///
-    /// Panics if the length of `l` or `u` is not the same as the number of problem constraints.
-    pub fn update_bounds(&mut self, l: &[float], u: &[float]) {
+    /// Returns an error if the length of `l` or `u` is not the same as the number of problem
+    /// constraints.
+    pub fn update_bounds(&mut self, l: &[float], u: &[float]) -> Result<(), UpdateError> {
+        check_len!("l", self.m, l.len());
+        check_len!("u", self.m, u.len());
         unsafe {
-            assert_eq!(self.m, l.len());
-            assert_eq!(self.m, u.len());
             check!(
-                update_bounds,
+                UpdateError,
                 ffi::osqp_update_data_vec(self.solver, ptr::null(), l.as_ptr(), u.as_ptr())
             );
         }
+        self.l.copy_from_slice(l);
+        self.u.copy_from_slice(u);
+        Ok(())
     }
 
     /// Sets the lower bound of the constraints to `l`.
     ///
-    /// Panics if the length of `l` is not the same as the number of problem constraints.
-    pub fn update_lower_bound(&mut self, l: &[float]) {
+    /// Returns an error if the length of `l` is not the same as the number of problem
+    /// constraints.
+    pub fn update_lower_bound(&mut self, l: &[float]) -> Result<(), UpdateError> {
+        check_len!("l", self.m, l.len());
         unsafe {
-            assert_eq!(self.m, l.len());
             check!(
-                update_lower_bound,
+                UpdateError,
                 ffi::osqp_update_data_vec(self.solver, ptr::null(), l.as_ptr(), ptr::null())
             );
         }
+        self.l.copy_from_slice(l);
+        Ok(())
     }
 
     /// Sets the upper bound of the constraints to `u`.
     ///
-    /// Panics if the length of `u` is not the same as the number of problem constraints.
-    pub fn update_upper_bound(&mut self, u: &[float]) {
+    /// Returns an error if the length of `u` is not the same as the number of problem
+    /// constraints.
+    pub fn update_upper_bound(&mut self, u: &[float]) -> Result<(), UpdateError> {
+        check_len!("u", self.m, u.len());
         unsafe {
-            assert_eq!(self.m, u.len());
             check!(
-                update_upper_bound,
+                UpdateError,
                 ffi::osqp_update_data_vec(self.solver, ptr::null(), ptr::null(), u.as_ptr())
             );
         }
+        self.u.copy_from_slice(u);
+        Ok(())
     }
 
     /// Warm starts the primal variables at `x` and the dual variables at `y`.
     ///
-    /// Panics if the length of `x` is not the same as the number of problem variables or the
-    /// length of `y` is not the same as the number of problem constraints.
-    pub fn warm_start(&mut self, x: &[float], y: &[float]) {
+    /// Returns an error if the length of `x` is not the same as the number of problem variables
+    /// or the length of `y` is not the same as the number of problem constraints.
+    pub fn warm_start(&mut self, x: &[float], y: &[float]) -> Result<(), UpdateError> {
+        check_len!("x", self.n, x.len());
+        check_len!("y", self.m, y.len());
         unsafe {
-            assert_eq!(self.n, x.len());
-            assert_eq!(self.m, y.len());
             check!(
-                warm_start,
+                UpdateError,
                 ffi::osqp_warm_start(self.solver, x.as_ptr(), y.as_ptr())
             );
         }
+        self.warm_start_x = Some(x.to_vec());
+        self.warm_start_y = Some(y.to_vec());
+        Ok(())
     }
 
     /// Warm starts the primal variables at `x`.
     ///
-    /// Panics if the length of `x` is not the same as the number of problem variables.
-    pub fn warm_start_x(&mut self, x: &[float]) {
+    /// Returns an error if the length of `x` is not the same as the number of problem variables.
+    pub fn warm_start_x(&mut self, x: &[float]) -> Result<(), UpdateError> {
+        check_len!("x", self.n, x.len());
         unsafe {
-            assert_eq!(self.n, x.len());
             check!(
-                warm_start_x,
+                UpdateError,
                 ffi::osqp_warm_start(self.solver, x.as_ptr(), ptr::null())
             );
         }
+        self.warm_start_x = Some(x.to_vec());
+        Ok(())
     }
 
     /// Warms start the dual variables at `y`.
     ///
-    /// Panics if the length of `y` is not the same as the number of problem constraints.
-    pub fn warm_start_y(&mut self, y: &[float]) {
+    /// Returns an error if the length of `y` is not the same as the number of problem
+    /// constraints.
+    pub fn warm_start_y(&mut self, y: &[float]) -> Result<(), UpdateError> {
+        check_len!("y", self.m, y.len());
         unsafe {
-            assert_eq!(self.m, y.len());
             check!(
-                warm_start_y,
+                UpdateError,
                 ffi::osqp_warm_start(self.solver, ptr::null(), y.as_ptr())
             );
         }
+        self.warm_start_y = Some(y.to_vec());
+        Ok(())
+    }
+
+    /// Resets the primal and dual variables to zero.
+    ///
+    /// This discards the iterate left by previous solves, which can slow down convergence after
+    /// the problem data has changed significantly.
+    pub fn cold_start(&mut self) {
+        unsafe {
+            ffi::osqp_cold_start(self.solver);
+        }
+        self.warm_start_x = Some(vec![0.0; self.n]);
+        self.warm_start_y = Some(vec![0.0; self.m]);
+    }
+
+    /// Returns a copy of the current primal and dual variables.
+    ///
+    /// These are the variables of the solution found by the most recent solve, including the last
+    /// iterate of a solve which stopped early, or those passed to a later call to `warm_start`,
+    /// `warm_start_x`, `warm_start_y` or `cold_start`. Passing the returned `x` and `y` to
+    /// `warm_start` restores this iterate.
+    ///
+    /// The variables of a solution are empty if `Settings::allocate_solution` is disabled.
+    pub fn iterate(&self) -> Iterate {
+        let (x, y) = unsafe {
+            (
+                solution_vec(self, |s| s.x, self.n),
+                solution_vec(self, |s| s.y, self.m),
+            )
+        };
+        let x = self.warm_start_x.as_deref().unwrap_or(x);
+        let y = self.warm_start_y.as_deref().unwrap_or(y);
+        Iterate {
+            x: x.to_vec(),
+            y: y.to_vec(),
+            z: if x.len() == self.n {
+                self.A.mul_vec(x)
+            } else {
+                Vec::new()
+            },
+        }
     }
 
     /// Updates the elements of matrix `P` without changing its sparsity structure.
     ///
-    /// Panics if the sparsity structure of `P` differs from the sparsity structure of the `P`
-    /// matrix provided to `Problem::new`.
+    /// Returns an error if the sparsity structure of `P` differs from the sparsity structure of
+    /// the `P` matrix provided to `Problem::new`.
     #[allow(non_snake_case)]
-    pub fn update_P<'a, T: Into<CscMatrix<'a>>>(&mut self, P: T) {
-        self.update_P_inner(P.into());
+    pub fn update_P<'a, T: Into<CscMatrix<'a>>>(&mut self, P: T) -> Result<(), UpdateError> {
+        self.update_P_inner(P.into())
     }
 
     #[allow(non_snake_case)]
-    fn update_P_inner(&mut self, P: CscMatrix) {
+    fn update_P_inner(&mut self, P: CscMatrix) -> Result<(), UpdateError> {
+        check_structure("P", &self.P, &P)?;
+        check_len!("P.data", self.P.data.len(), P.data.len());
         unsafe {
-            // TODO: can no longer get current P/q/A/l/u from the solver?
-            // let P_ffi = CscMatrix::from_ffi((*(*self.solver).work).P);
-            // P.assert_same_sparsity_structure(&P_ffi);
-
             check!(
-                update_P,
-                ffi::osqp_update_data_mat(
+                UpdateError,
+                self.with_memory(|| ffi::osqp_update_data_mat(
                     self.solver,
                     P.data.as_ptr(),
                     ptr::null(),
@@ -333,30 +500,30 @@ impl Problem {
                     ptr::null(),
                     ptr::null(),
                     0
-                )
+                ))
             );
         }
+        self.P.data.to_mut().copy_from_slice(&P.data);
+        Ok(())
     }
 
     /// Updates the elements of matrix `A` without changing its sparsity structure.
     ///
-    /// Panics if the sparsity structure of `A` differs from the sparsity structure of the `A`
-    /// matrix provided to `Problem::new`.
+    /// Returns an error if the sparsity structure of `A` differs from the sparsity structure of
+    /// the `A` matrix provided to `Problem::new`.
     #[allow(non_snake_case)]
-    pub fn update_A<'a, T: Into<CscMatrix<'a>>>(&mut self, A: T) {
-        self.update_A_inner(A.into());
+    pub fn update_A<'a, T: Into<CscMatrix<'a>>>(&mut self, A: T) -> Result<(), UpdateError> {
+        self.update_A_inner(A.into())
     }
 
     #[allow(non_snake_case)]
-    fn update_A_inner(&mut self, A: CscMatrix) {
+    fn update_A_inner(&mut self, A: CscMatrix) -> Result<(), UpdateError> {
+        check_structure("A", &self.A, &A)?;
+        check_len!("A.data", self.A.data.len(), A.data.len());
         unsafe {
-            // TODO: can no longer get current P/q/A/l/u from the solver?
-            // let A_ffi = CscMatrix::from_ffi((*(*self.workspace).data).A);
-            // A.assert_same_sparsity_structure(&A_ffi);
-
             check!(
-                update_A,
-                ffi::osqp_update_data_mat(
+                UpdateError,
+                self.with_memory(|| ffi::osqp_update_data_mat(
                     self.solver,
                     ptr::null(),
                     ptr::null(),
@@ -364,31 +531,435 @@ impl Problem {
                     A.data.as_ptr(),
                     ptr::null(),
                     A.data.len() as ffi::osqp_int,
-                )
+                ))
             );
         }
+        self.A.data.to_mut().copy_from_slice(&A.data);
+        Ok(())
     }
 
+    /// Updates the elements of matrix `P` at the given positions in its data array.
+    ///
+    /// `P.data[indices[i]]` is set to `values[i]` for each `i`, where `P` is the upper triangular
+    /// matrix provided to `Problem::new`. The positions of individual elements can be found using
+    /// `Problem::P_entry_index`.
+    ///
+    /// Only the changed elements are passed to OSQP, however OSQP may still need to refactorise
+    /// the KKT matrix.
+    ///
+    /// Returns an error if `indices` and `values` have different lengths or if any index is out of
+    /// bounds.
     #[allow(non_snake_case)]
-    pub fn update_settings(&mut self, settings: &Settings) {
+    pub fn update_P_entries(
+        &mut self,
+        indices: &[usize],
+        values: &[float],
+    ) -> Result<(), UpdateError> {
+        check_entries("P", &self.P, indices, values)?;
+        if indices.is_empty() {
+            return Ok(());
+        }
+        // Converting is safe as all indices are smaller than the number of elements in P, which
+        // fits in osqp_int.
+        let ffi_indices = ffi_indices(indices);
+        unsafe {
+            check!(
+                UpdateError,
+                self.with_memory(|| ffi::osqp_update_data_mat(
+                    self.solver,
+                    values.as_ptr(),
+                    ffi_indices.as_ptr(),
+                    values.len() as ffi::osqp_int,
+                    ptr::null(),
+                    ptr::null(),
+                    0
+                ))
+            );
+        }
+        let data = self.P.data.to_mut();
+        for (&idx, &value) in indices.iter().zip(values) {
+            data[idx] = value;
+        }
+        Ok(())
+    }
+
+    /// Updates the elements of matrix `A` at the given positions in its data array.
+    ///
+    /// `A.data[indices[i]]` is set to `values[i]` for each `i`, where `A` is the matrix provided
+    /// to `Problem::new`. The positions of individual elements can be found using
+    /// `Problem::A_entry_index`.
+    ///
+    /// Only the changed elements are passed to OSQP, however OSQP may still need to refactorise
+    /// the KKT matrix.
+    ///
+    /// Returns an error if `indices` and `values` have different lengths or if any index is out of
+    /// bounds.
+    #[allow(non_snake_case)]
+    pub fn update_A_entries(
+        &mut self,
+        indices: &[usize],
+        values: &[float],
+    ) -> Result<(), UpdateError> {
+        check_entries("A", &self.A, indices, values)?;
+        if indices.is_empty() {
+            return Ok(());
+        }
+        // Converting is safe as all indices are smaller than the number of elements in A, which
+        // fits in osqp_int.
+        let ffi_indices = ffi_indices(indices);
+        unsafe {
+            check!(
+                UpdateError,
+                self.with_memory(|| ffi::osqp_update_data_mat(
+                    self.solver,
+                    ptr::null(),
+                    ptr::null(),
+                    0,
+                    values.as_ptr(),
+                    ffi_indices.as_ptr(),
+                    values.len() as ffi::osqp_int,
+                ))
+            );
+        }
+        let data = self.A.data.to_mut();
+        for (&idx, &value) in indices.iter().zip(values) {
+            data[idx] = value;
+        }
+        Ok(())
+    }
+
+    /// Returns the position in the data array of `P` of the element at (`row`, `col`).
+    ///
+    /// Returns `None` if the element is not part of the sparsity structure of the upper triangular
+    /// `P` matrix provided to `Problem::new`. In particular this is the case for all elements
+    /// below the diagonal.
+    #[allow(non_snake_case)]
+    pub fn P_entry_index(&self, row: usize, col: usize) -> Option<usize> {
+        self.P.data_index(row, col)
+    }
+
+    /// Returns the position in the data array of `A` of the element at (`row`, `col`).
+    ///
+    /// Returns `None` if the element is not part of the sparsity structure of the `A` matrix
+    /// provided to `Problem::new`.
+    #[allow(non_snake_case)]
+    pub fn A_entry_index(&self, row: usize, col: usize) -> Option<usize> {
+        self.A.data_index(row, col)
+    }
+
+    /// Returns the problem data currently used by the solver.
+    ///
+    /// This is the data provided to `Problem::new` with all subsequent updates applied. Infinite
+    /// bounds are returned as provided rather than as the large finite value used by OSQP.
+    pub fn data(&self) -> ProblemData<'_> {
+        ProblemData {
+            P: (&self.P).into(),
+            q: &self.q,
+            A: (&self.A).into(),
+            l: &self.l,
+            u: &self.u,
+        }
+    }
+
+    /// Updates the solver settings.
+    ///
+    /// Returns an error if a setting which can only be changed during setup, such as `sigma` or
+    /// `scaling`, differs from the value used by the solver. An `adaptive_rho_interval` of zero
+    /// keeps the interval chosen by OSQP during setup. `rho` is updated using
+    /// `Problem::update_rho` if it differs from the value used by the solver.
+    #[allow(non_snake_case)]
+    pub fn update_settings(&mut self, settings: &Settings) -> Result<(), UpdateError> {
+        settings
+            .validate_update()
+            .map_err(UpdateError::InvalidSetting)?;
+        let current = Settings::from_problem(self);
+        if let Some(name) = settings.setup_only_difference(&current) {
+            return Err(UpdateError::SetupOnlySetting(name));
+        }
+        let rho = settings.get_rho();
         let settings = &settings.inner as *const ffi::OSQPSettings;
         unsafe {
             check!(
-                update_settings,
+                UpdateError,
                 ffi::osqp_update_settings(self.solver, settings)
             );
         }
+        if rho != current.get_rho() {
+            self.update_rho(rho)?;
+        }
+        Ok(())
     }
 
     /// Attempts to solve the quadratic program.
-    pub fn solve<'a>(&'a mut self) -> Status<'a> {
+    ///
+    /// Returns an error if OSQP fails to run. A problem which is infeasible or could not be
+    /// solved to the requested accuracy is not an error and is instead reported by the returned
+    /// `Status`.
+    pub fn solve<'a>(&'a mut self) -> Result<Status<'a>, SolveError> {
+        if self.record_history {
+            self.solve_recording_history()?;
+        } else {
+            self.history = None;
+            self.start_solve();
+            self.solve_once()?;
+        }
+        Ok(Status::from_problem(self))
+    }
+
+    /// Attempts to solve the quadratic program, returning a copy of the result which does not
+    /// borrow the problem.
+    ///
+    /// This is equivalent to calling `Status::to_owned` on the result of `Problem::solve`.
+    pub fn solve_owned(&mut self) -> Result<OwnedStatus, SolveError> {
+        self.solve().map(|status| status.to_owned())
+    }
+
+    /// Computes the derivatives of a loss function with respect to the problem data.
+    ///
+    /// `dx` and `dy` are the derivatives of the loss with respect to the primal and dual
+    /// variables of the solution found by the most recent solve. The derivatives with respect
+    /// to `P` and `A` are returned for the non-zero elements of the matrices provided to
+    /// `Problem::new`.
+    ///
+    /// Returns an error if the problem has not been solved or if the length of `dx` is not the
+    /// same as the number of problem variables or the length of `dy` is not the same as the
+    /// number of problem constraints.
+    #[allow(non_snake_case)]
+    pub fn adjoint_derivative(
+        &mut self,
+        dx: &[float],
+        dy: &[float],
+    ) -> Result<Derivatives, DerivativeError> {
+        if dx.len() != self.n {
+            return Err(DerivativeError::DimensionMismatch {
+                name: "dx",
+                expected: self.n,
+                found: dx.len(),
+            });
+        }
+        if dy.len() != self.m {
+            return Err(DerivativeError::DimensionMismatch {
+                name: "dy",
+                expected: self.m,
+                found: dy.len(),
+            });
+        }
+
+        unsafe {
+            let status_val = (*(*self.solver).info).status_val;
+            if status_val as ffi::osqp_status_type != ffi::OSQP_SOLVED {
+                return Err(DerivativeError::NotSolved);
+            }
+
+            // OSQP takes mutable pointers but does not modify `dx` or `dy`
+            let mut dx = dx.to_vec();
+            let mut dy = dy.to_vec();
+            check!(
+                DerivativeError,
+                self.with_memory(|| ffi::osqp_adjoint_derivative_compute(
+                    self.solver,
+                    dx.as_mut_ptr(),
+                    dy.as_mut_ptr()
+                ))
+            );
+
+            let mut dP = self.P.clone();
+            let mut dA = self.A.clone();
+            {
+                // Both matrices were accepted by `Problem::new` so they fit in `osqp_int`
+                let dP_ffi = dP
+                    .to_ffi_mut()
+                    .expect("P is too large for the OSQP integer type");
+                let dA_ffi = dA
+                    .to_ffi_mut()
+                    .expect("A is too large for the OSQP integer type");
+                check!(
+                    DerivativeError,
+                    ffi::osqp_adjoint_derivative_get_mat(
+                        self.solver,
+                        dP_ffi.as_ptr(),
+                        dA_ffi.as_ptr()
+                    )
+                );
+            }
+
+            let mut dq = vec![0.0; self.n];
+            let mut dl = vec![0.0; self.m];
+            let mut du = vec![0.0; self.m];
+            check!(
+                DerivativeError,
+                ffi::osqp_adjoint_derivative_get_vec(
+                    self.solver,
+                    dq.as_mut_ptr(),
+                    dl.as_mut_ptr(),
+                    du.as_mut_ptr()
+                )
+            );
+
+            Ok(Derivatives { dP, dq, dA, dl, du })
+        }
+    }
+
+    /// Returns a handle which can be used to cancel a running solve from another thread.
+    ///
+    /// No signal handlers are installed, a solve is only interrupted by calling
+    /// `InterruptHandle::cancel`.
+    #[cfg(feature = "interrupt")]
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        InterruptHandle {
+            flag: self.interrupt.clone(),
+        }
+    }
+
+    /// Resets the state kept for a solve which may run in several steps.
+    pub(crate) fn start_solve(&mut self) {
+        self.steps_info = None;
+        self.steps_interrupted = false;
+        // Only cancellations made during this solve should interrupt it
+        #[cfg(feature = "interrupt")]
+        self.interrupt.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns whether the running solve has been cancelled using an `InterruptHandle`.
+    #[cfg(feature = "interrupt")]
+    pub(crate) fn interrupt_requested(&self) -> bool {
+        self.interrupt.load(Ordering::SeqCst)
+    }
+
+    #[cfg(not(feature = "interrupt"))]
+    pub(crate) fn interrupt_requested(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "interrupt")]
+    unsafe fn run_solver(&self) -> ffi::osqp_int {
+        let solver = self.solver;
+        ffi::interrupt::with_interrupt_flag(&self.interrupt, || ffi::osqp_solve(solver))
+    }
+
+    #[cfg(not(feature = "interrupt"))]
+    unsafe fn run_solver(&self) -> ffi::osqp_int {
+        ffi::osqp_solve(self.solver)
+    }
+
+    /// Returns the number of bytes currently allocated by OSQP for this solver.
+    ///
+    /// This includes the workspace allocated during setup and any memory allocated since, for
+    /// example to compute derivatives, but not the copy of the problem data kept by this wrapper.
+    /// This is always zero when linking a system OSQP library using the `system` feature.
+    pub fn memory_usage(&self) -> usize {
+        self.memory.load(Ordering::Relaxed)
+    }
+
+    /// Runs the solver once without recording a `ConvergenceHistory`.
+    pub(crate) fn solve_once(&mut self) -> Result<(), SolveError> {
+        self.warm_start_x = None;
+        self.warm_start_y = None;
         unsafe {
-            check!(solve, ffi::osqp_solve(self.solver));
-            Status::from_problem(self)
+            let ret = self.with_memory(|| self.run_solver());
+            // OSQP reports an interrupted solve using both the status and the exit code
+            let status_val = (*(*self.solver).info).status_val;
+            if ret != 0 && status_val as ffi::osqp_status_type != ffi::OSQP_SIGINT {
+                return Err(SolveError::Osqp(OsqpError::from_ffi(ret)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Calls `f` with memory allocated by OSQP on this thread attributed to this solver.
+    pub(crate) unsafe fn with_memory<R, F: FnOnce() -> R>(&self, f: F) -> R {
+        ffi::memory::with_allocation_counter(&self.memory, f)
+    }
+}
+
+/// A view of the data of a quadratic program.
+///
+/// Returned by `Problem::data`.
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProblemData<'a> {
+    /// The upper triangular part of the quadratic cost matrix.
+    pub P: CscMatrix<'a>,
+    /// The linear part of the cost function.
+    pub q: &'a [float],
+    /// The constraint matrix.
+    pub A: CscMatrix<'a>,
+    /// The lower bound of the constraints.
+    pub l: &'a [float],
+    /// The upper bound of the constraints.
+    pub u: &'a [float],
+}
+
+/// The primal and dual variables of a quadratic program.
+///
+/// Returned by `Problem::iterate`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Iterate {
+    /// The primal variables.
+    pub x: Vec<float>,
+    /// The dual variables.
+    pub y: Vec<float>,
+    /// The constraint values `Ax` at the primal variables.
+    pub z: Vec<float>,
+}
+
+/// The derivatives of a loss function with respect to the data of a quadratic program.
+///
+/// Returned by `Problem::adjoint_derivative`.
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Derivatives {
+    /// The derivatives with respect to the non-zero elements of the upper triangular part of
+    /// `P`.
+    pub dP: CscMatrix<'static>,
+    /// The derivatives with respect to `q`.
+    pub dq: Vec<float>,
+    /// The derivatives with respect to the non-zero elements of `A`.
+    pub dA: CscMatrix<'static>,
+    /// The derivatives with respect to `l`.
+    pub dl: Vec<float>,
+    /// The derivatives with respect to `u`.
+    pub du: Vec<float>,
+}
+
+fn check_structure(
+    name: &'static str,
+    expected: &CscMatrix,
+    found: &CscMatrix,
+) -> Result<(), UpdateError> {
+    match expected.check_same_sparsity_structure(found) {
+        Ok(()) => Ok(()),
+        Err(StructureMismatch::Shape) => Err(UpdateError::ShapeMismatch {
+            name,
+            expected: (expected.nrows, expected.ncols),
+            found: (found.nrows, found.ncols),
+        }),
+        Err(StructureMismatch::Column(column)) => {
+            Err(UpdateError::SparsityMismatch { name, column })
         }
     }
 }
 
+fn check_entries(
+    name: &'static str,
+    mat: &CscMatrix,
+    indices: &[usize],
+    values: &[float],
+) -> Result<(), UpdateError> {
+    check_len!("values", indices.len(), values.len());
+    let len = mat.data.len();
+    if let Some(&index) = indices.iter().find(|&&index| index >= len) {
+        return Err(UpdateError::IndexOutOfBounds { name, index, len });
+    }
+    Ok(())
+}
+
+/// Widens an integer returned by OSQP, which is only narrower than `i64` with the `i32` feature.
+#[allow(clippy::useless_conversion)]
+pub(crate) fn int_to_i64(value: ffi::osqp_int) -> i64 {
+    i64::from(value)
+}
+
 impl Drop for Problem {
     fn drop(&mut self) {
         unsafe {
@@ -404,6 +975,10 @@ unsafe impl Sync for Problem {}
 #[derive(Debug)]
 pub enum SetupError {
     DataInvalid(&'static str),
+    /// A setting has an invalid value, see `Settings::validate`.
+    InvalidSetting(SettingsError),
+    /// The linear system solver is not available in this build of OSQP, see `capabilities`.
+    LinsysSolverUnavailable(LinsysSolver),
     SettingsInvalid,
     LinsysSolverLoadFailed,
     LinsysSolverInitFailed,
@@ -425,6 +1000,12 @@ impl fmt::Display for SetupError {
                 }
                 Ok(())
             }
+            SetupError::InvalidSetting(err) => write!(f, "problem settings invalid: {}", err),
+            SetupError::LinsysSolverUnavailable(solver) => write!(
+                f,
+                "linear system solver {:?} not available in this build of OSQP",
+                solver
+            ),
             SetupError::SettingsInvalid => "problem settings invalid".fmt(f),
             SetupError::LinsysSolverLoadFailed => "linear system solver failed to load".fmt(f),
             SetupError::LinsysSolverInitFailed => {
@@ -439,14 +1020,251 @@ impl fmt::Display for SetupError {
 
 impl Error for SetupError {}
 
+/// An error reported by OSQP through the exit code of one of its functions.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OsqpError {
+    DataInvalid,
+    SettingsInvalid,
+    LinsysSolverLoadFailed,
+    LinsysSolverInitFailed,
+    NonConvex,
+    MemoryAllocationFailed,
+    WorkspaceNotInitialised,
+    FileOpenFailed,
+    CodegenDefinesInvalid,
+    DataNotInitialised,
+    FunctionNotImplemented,
+    /// An exit code not recognised by this version of the wrapper.
+    Unknown(i64),
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl OsqpError {
+    pub(crate) fn from_ffi(ret: ffi::osqp_int) -> OsqpError {
+        match ret as ffi::osqp_error_type {
+            ffi::OSQP_DATA_VALIDATION_ERROR => OsqpError::DataInvalid,
+            ffi::OSQP_SETTINGS_VALIDATION_ERROR => OsqpError::SettingsInvalid,
+            ffi::OSQP_LINSYS_SOLVER_INIT_ERROR => OsqpError::LinsysSolverInitFailed,
+            ffi::OSQP_NONCVX_ERROR => OsqpError::NonConvex,
+            ffi::OSQP_MEM_ALLOC_ERROR => OsqpError::MemoryAllocationFailed,
+            ffi::OSQP_WORKSPACE_NOT_INIT_ERROR => OsqpError::WorkspaceNotInitialised,
+            ffi::OSQP_ALGEBRA_LOAD_ERROR => OsqpError::LinsysSolverLoadFailed,
+            ffi::OSQP_FOPEN_ERROR => OsqpError::FileOpenFailed,
+            ffi::OSQP_CODEGEN_DEFINES_ERROR => OsqpError::CodegenDefinesInvalid,
+            ffi::OSQP_DATA_NOT_INITIALIZED => OsqpError::DataNotInitialised,
+            ffi::OSQP_FUNC_NOT_IMPLEMENTED => OsqpError::FunctionNotImplemented,
+            _ => OsqpError::Unknown(int_to_i64(ret)),
+        }
+    }
+}
+
+impl fmt::Display for OsqpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OsqpError::DataInvalid => "problem data invalid".fmt(f),
+            OsqpError::SettingsInvalid => "problem settings invalid".fmt(f),
+            OsqpError::LinsysSolverLoadFailed => "linear system solver failed to load".fmt(f),
+            OsqpError::LinsysSolverInitFailed => "linear system solver failed to initialise".fmt(f),
+            OsqpError::NonConvex => "problem non-convex".fmt(f),
+            OsqpError::MemoryAllocationFailed => "memory allocation failed".fmt(f),
+            OsqpError::WorkspaceNotInitialised => "solver workspace not initialised".fmt(f),
+            OsqpError::FileOpenFailed => "failed to open file".fmt(f),
+            OsqpError::CodegenDefinesInvalid => "code generation defines invalid".fmt(f),
+            OsqpError::DataNotInitialised => "problem data not initialised".fmt(f),
+            OsqpError::FunctionNotImplemented => {
+                "function not implemented in this build of OSQP".fmt(f)
+            }
+            OsqpError::Unknown(code) => write!(f, "unknown error with exit code {}", code),
+            OsqpError::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+impl Error for OsqpError {}
+
+/// An error that can occur when updating the problem data, the settings or the warm start
+/// variables of a solver.
+#[derive(Debug)]
+#[allow(clippy::manual_non_exhaustive)]
+pub enum UpdateError {
+    /// The length of a vector, or the number of non-zero elements of a matrix, is inconsistent
+    /// with the problem dimensions.
+    DimensionMismatch {
+        name: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// The shape of an updated matrix differs from the shape of the matrix provided to
+    /// `Problem::new`. Shapes are given as `(nrows, ncols)`.
+    ShapeMismatch {
+        name: &'static str,
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// The sparsity structure of an updated matrix differs from the sparsity structure of the
+    /// matrix provided to `Problem::new`. `column` is the first column which differs.
+    SparsityMismatch { name: &'static str, column: usize },
+    /// An index into the data array of a matrix is larger than the number of non-zero elements
+    /// in the matrix.
+    IndexOutOfBounds {
+        name: &'static str,
+        index: usize,
+        len: usize,
+    },
+    /// A setting has an invalid value, see `Settings::validate`.
+    InvalidSetting(SettingsError),
+    /// A setting which can only be changed during setup differs from the value used by the
+    /// solver.
+    SetupOnlySetting(&'static str),
+    /// OSQP returned an error.
+    Osqp(OsqpError),
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpdateError::DimensionMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "dimension mismatch: expected {} to have length {} but found {}",
+                name, expected, found
+            ),
+            UpdateError::ShapeMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "shape mismatch: expected {} to be {}x{} but found {}x{}",
+                name, expected.0, expected.1, found.0, found.1
+            ),
+            UpdateError::SparsityMismatch { name, column } => write!(
+                f,
+                "sparsity structure of {} differs from the structure provided at setup in column {}",
+                name, column
+            ),
+            UpdateError::IndexOutOfBounds { name, index, len } => write!(
+                f,
+                "index {} out of bounds for {} with {} non-zero elements",
+                index, name, len
+            ),
+            UpdateError::InvalidSetting(err) => write!(f, "problem settings invalid: {}", err),
+            UpdateError::SetupOnlySetting(name) => {
+                write!(f, "{} can only be changed during setup", name)
+            }
+            UpdateError::Osqp(err) => err.fmt(f),
+            UpdateError::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+impl Error for UpdateError {}
+
+/// An error that can occur when running the solver.
+///
+/// Failing to find a solution is not an error, see `Status` for the possible outcomes of a
+/// solve.
+#[derive(Debug)]
+#[allow(clippy::manual_non_exhaustive)]
+pub enum SolveError {
+    /// OSQP returned an error.
+    Osqp(OsqpError),
+    /// The settings are not supported while recording a `ConvergenceHistory`, or the settings
+    /// used to record it could not be applied.
+    HistorySettings(UpdateError),
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Osqp(err) => err.fmt(f),
+            SolveError::HistorySettings(err) => {
+                write!(f, "failed to update settings to record history: {}", err)
+            }
+            SolveError::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+/// An error that can occur when computing derivatives using `Problem::adjoint_derivative`.
+#[derive(Debug)]
+#[allow(clippy::manual_non_exhaustive)]
+pub enum DerivativeError {
+    /// A vector does not have the expected length.
+    DimensionMismatch {
+        name: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// The most recent solve did not find a solution.
+    NotSolved,
+    /// OSQP returned an error.
+    Osqp(OsqpError),
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl fmt::Display for DerivativeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DerivativeError::DimensionMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "dimension mismatch: expected {} to have length {} but found {}",
+                name, expected, found
+            ),
+            DerivativeError::NotSolved => "problem has not been solved".fmt(f),
+            DerivativeError::Osqp(err) => err.fmt(f),
+            DerivativeError::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+impl Error for DerivativeError {}
+
 #[cfg(test)]
+// Expected solutions are written in double precision
+#[cfg_attr(feature = "f32", allow(clippy::excessive_precision))]
 mod tests {
     use std::iter;
 
     use super::*;
 
-    #[test]
+    // Solutions are compared with a looser tolerance in single precision
+    #[cfg(not(feature = "f32"))]
+    const TOL: float = 1e-9;
+    #[cfg(feature = "f32")]
+    const TOL: float = 1e-4;
+
+    /// Sets up the example problem from the OSQP documentation using `settings`.
     #[allow(non_snake_case)]
+    pub(crate) fn example_problem(settings: &Settings) -> Result<Problem, SetupError> {
+        let P = CscMatrix::from(&[[4.0, 1.0], [1.0, 2.0]]).into_upper_tri();
+        let q = &[1.0, 1.0];
+        let A = &[[1.0, 1.0], [1.0, 0.0], [0.0, 1.0]];
+        let l = &[1.0, 0.0, 0.0];
+        let u = &[1.0, 0.7, 0.7];
+        Problem::new(&P, q, A, l, u, settings)
+    }
+
+    #[test]
     fn update_settings() {
         // Directly update some settings using methods on the Settings object
 
@@ -459,21 +1277,13 @@ mod tests {
     }
 
     #[test]
-    #[allow(non_snake_case)]
     fn update_rho() {
         // Update a setting using a method generated by the settings macro
 
-        // Define problem data
-        let P = CscMatrix::from(&[[4.0, 1.0], [1.0, 2.0]]).into_upper_tri();
-        let q = &[1.0, 1.0];
-        let A = &[[1.0, 1.0], [1.0, 0.0], [0.0, 1.0]];
-        let l = &[1.0, 0.0, 0.0];
-        let u = &[1.0, 0.7, 0.7];
-
         let settings = Settings::default().verbose(false);
-        let mut prob = Problem::new(&P, q, A, l, u, &settings).unwrap();
+        let mut prob = example_problem(&settings).unwrap();
 
-        prob.update_rho(0.7);
+        prob.update_rho(0.7).unwrap();
         unsafe {
             let solver_ref = prob.solver.as_ref().unwrap();
             let settings_ref = solver_ref.settings.as_ref().unwrap();
@@ -484,22 +1294,14 @@ mod tests {
     }
 
     #[test]
-    #[allow(non_snake_case)]
     fn update_prob_settings() {
         // Update some settings using update_settings on the Problem object
 
-        // Define problem data
-        let P = CscMatrix::from(&[[4.0, 1.0], [1.0, 2.0]]).into_upper_tri();
-        let q = &[1.0, 1.0];
-        let A = &[[1.0, 1.0], [1.0, 0.0], [0.0, 1.0]];
-        let l = &[1.0, 0.0, 0.0];
-        let u = &[1.0, 0.7, 0.7];
-
         let settings = Settings::default().verbose(false);
-        let mut prob = Problem::new(&P, q, A, l, u, &settings).unwrap();
+        let mut prob = example_problem(&settings).unwrap();
 
         let settings = settings.max_iter(1_000_000);
-        prob.update_settings(&settings);
+        prob.update_settings(&settings).unwrap();
         unsafe {
             let solver_ref = prob.solver.as_ref().unwrap();
             let settings_ref = solver_ref.settings.as_ref().unwrap();
@@ -528,13 +1330,347 @@ mod tests {
 
         // Check updating P and A separately
         let mut prob = Problem::new(&P_wrong, q, A_wrong, l, u, &settings).unwrap();
-        prob.update_P(&P);
-        prob.update_A(A);
-        let result = prob.solve();
+        prob.update_P(&P).unwrap();
+        prob.update_A(A).unwrap();
+        let result = prob.solve().unwrap();
+        let x = result.solution().unwrap().x();
+        let expected = &[0.2987710845986426, 0.701227995544065];
+        assert_eq!(expected.len(), x.len());
+        assert!(expected.iter().zip(x).all(|(&a, &b)| (a - b).abs() < TOL));
+    }
+
+    #[test]
+    fn cold_start_and_iterate() {
+        let settings = Settings::default()
+            .verbose(false)
+            .polishing(false)
+            .check_termination(Some(1));
+        let mut prob = example_problem(&settings).unwrap();
+
+        let first = prob.solve_owned().unwrap();
+        let saved = prob.iterate();
+        assert_eq!(&*saved.x, first.x().unwrap());
+        assert_eq!(&*saved.y, first.solution().unwrap().y());
+        assert_eq!(saved.z, prob.data().A.mul_vec(&saved.x));
+
+        prob.cold_start();
+        let cold = prob.iterate();
+        assert!(cold
+            .x
+            .iter()
+            .chain(&cold.y)
+            .chain(&cold.z)
+            .all(|&v| v == 0.0));
+
+        let cold_iter = prob.solve_owned().unwrap().info().iter();
+
+        prob.warm_start(&saved.x, &saved.y).unwrap();
+        assert_eq!(prob.iterate(), saved);
+        let warm_iter = prob.solve_owned().unwrap().info().iter();
+        assert!(warm_iter < cold_iter);
+    }
+
+    #[test]
+    fn iterate_after_max_iter() {
+        let settings = Settings::default()
+            .verbose(false)
+            .polishing(false)
+            .check_termination(Some(1))
+            .max_iter(3);
+        let mut prob = example_problem(&settings).unwrap();
+
+        let status = prob.solve_owned().unwrap();
+        let solution = match status {
+            OwnedStatus::MaxIterationsReached(solution) => solution,
+            other => panic!("expected MaxIterationsReached but found {:?}", other),
+        };
+        let iterate = prob.iterate();
+        assert_eq!(iterate.x, solution.x());
+        assert_eq!(iterate.y, solution.y());
+        assert_eq!(iterate.z, prob.data().A.mul_vec(solution.x()));
+        assert_eq!(iterate.z.len(), 3);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn adjoint_derivative() {
+        // With inactive constraints x = -inv(P) q so the derivative with respect to q is
+        // -inv(P) dx
+        let P = CscMatrix::from(&[[4.0, 1.0], [1.0, 2.0]]).into_upper_tri();
+        let q = &[1.0, 1.0];
+        let A = &[[1.0, 0.0], [0.0, 1.0]];
+        let l = &[-10.0, -10.0];
+        let u = &[10.0, 10.0];
+
+        let settings = Settings::default().verbose(false).eps_abs(TOL).eps_rel(TOL);
+        let mut prob = Problem::new(&P, q, A, l, u, &settings).unwrap();
+
+        match prob.adjoint_derivative(&[1.0, 0.0], &[0.0, 0.0]) {
+            Err(DerivativeError::NotSolved) => (),
+            other => panic!("expected NotSolved but found {:?}", other),
+        }
+
+        prob.solve().unwrap().solution().unwrap();
+
+        match prob.adjoint_derivative(&[1.0], &[0.0, 0.0]) {
+            Err(DerivativeError::DimensionMismatch { name: "dx", .. }) => (),
+            other => panic!("expected DimensionMismatch but found {:?}", other),
+        }
+
+        let derivatives = prob.adjoint_derivative(&[1.0, 0.0], &[0.0, 0.0]).unwrap();
+        let expected_dq = [-2.0 / 7.0, 1.0 / 7.0];
+        assert!(expected_dq
+            .iter()
+            .zip(&derivatives.dq)
+            .all(|(&a, &b)| (a - b).abs() < 1e-4));
+        assert!(derivatives
+            .dl
+            .iter()
+            .chain(&derivatives.du)
+            .all(|&v| v.abs() < 1e-4));
+        assert!(derivatives.dP.check_same_sparsity_structure(&P).is_ok());
+        assert!(derivatives
+            .dA
+            .check_same_sparsity_structure(&CscMatrix::from(A))
+            .is_ok());
+    }
+
+    #[test]
+    fn solution_not_allocated() {
+        let settings = Settings::default().verbose(false).allocate_solution(false);
+        let mut prob = example_problem(&settings).unwrap();
+
+        prob.warm_start(&[0.5, 0.5], &[0.0, 0.0, 0.0]).unwrap();
+        prob.cold_start();
+        assert_eq!(prob.iterate().x, &[0.0, 0.0]);
+
+        let status = prob.solve().unwrap();
+        let solution = status.solution().unwrap();
+        assert_eq!(solution.x(), &[]);
+        assert_eq!(solution.y(), &[]);
+        assert_eq!(prob.iterate().x, Vec::<float>::new());
+    }
+
+    #[cfg(all(feature = "printing", not(feature = "system")))]
+    #[test]
+    fn print_sink() {
+        use std::sync::{Arc, Mutex};
+
+        // Only lines printed on this thread are captured as other tests may run concurrently
+        let thread = std::thread::current().id();
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink_lines = lines.clone();
+        set_print_sink(move |level, line| {
+            if std::thread::current().id() == thread {
+                sink_lines.lock().unwrap().push((level, line.to_owned()));
+            }
+        });
+
+        let settings = Settings::default().verbose(true);
+        let mut prob = example_problem(&settings).unwrap();
+        prob.solve().unwrap();
+        reset_print_sink();
+
+        let lines = lines.lock().unwrap();
+        assert!(lines.iter().all(|(_, line)| !line.contains('\n')));
+        assert!(lines
+            .iter()
+            .any(|&(level, ref line)| level == PrintLevel::Info && line.contains("solved")));
+    }
+
+    #[test]
+    fn unavailable_linsys_solver() {
+        if capabilities().contains(Capabilities::INDIRECT_SOLVER) {
+            return;
+        }
+
+        let settings = Settings::default()
+            .verbose(false)
+            .linsys_solver(LinsysSolver::Indirect);
+        match example_problem(&settings) {
+            Err(SetupError::LinsysSolverUnavailable(LinsysSolver::Indirect)) => (),
+            _ => panic!("expected LinsysSolverUnavailable"),
+        }
+    }
+
+    #[cfg(not(feature = "system"))]
+    #[test]
+    fn memory_usage() {
+        let settings = Settings::default().verbose(false);
+        let mut small = example_problem(&settings).unwrap();
+        let setup_usage = small.memory_usage();
+        assert!(setup_usage > 0);
+
+        small.solve().unwrap();
+        small.adjoint_derivative(&[1.0, 0.0], &[0.0; 3]).unwrap();
+        assert!(small.memory_usage() >= setup_usage);
+
+        // Memory allocated by one solver is not attributed to another
+        let n = 50;
+        let identity = || {
+            let diagonal = (0..n * n).map(|i| if i % (n + 1) == 0 { 1.0 } else { 0.0 });
+            CscMatrix::from_column_iter_dense(n, n, diagonal)
+        };
+        let (q, l, u) = (vec![1.0; n], vec![-1.0; n], vec![1.0; n]);
+        let large = Problem::new(identity(), &q, identity(), &l, &u, &settings).unwrap();
+        assert!(large.memory_usage() > setup_usage);
+        small.solve().unwrap();
+        drop(large);
+        assert!(small.memory_usage() >= setup_usage);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn problem_data_tracks_updates() {
+        let settings = Settings::default().verbose(false);
+        let mut prob = example_problem(&settings).unwrap();
+        {
+            let data = prob.data();
+            let P = CscMatrix::from(&[[4.0, 1.0], [1.0, 2.0]]).into_upper_tri();
+            assert_eq!(data.P, P);
+            assert_eq!(data.q, &[1.0, 1.0]);
+            assert_eq!(
+                data.A,
+                CscMatrix::from(&[[1.0, 1.0], [1.0, 0.0], [0.0, 1.0]])
+            );
+            assert_eq!(data.l, &[1.0, 0.0, 0.0]);
+            assert_eq!(data.u, &[1.0, 0.7, 0.7]);
+        }
+
+        let P_new = CscMatrix::from(&[[5.0, 2.0], [2.0, 3.0]]).into_upper_tri();
+        let A_new = &[[2.0, 1.0], [1.0, 0.0], [0.0, 3.0]];
+        prob.update_P(&P_new).unwrap();
+        prob.update_A(A_new).unwrap();
+        prob.update_lin_cost(&[0.5, -1.0]).unwrap();
+        prob.update_lower_bound(&[0.5, -1.0, -1.0]).unwrap();
+        prob.update_upper_bound(&[2.0, 1.0, 1.0]).unwrap();
+        let idx = prob.A_entry_index(2, 1).unwrap();
+        prob.update_A_entries(&[idx], &[4.0]).unwrap();
+
+        // Failed updates must not change the data
+        assert!(prob.update_lin_cost(&[1.0]).is_err());
+
+        let data = prob.data();
+        assert_eq!(data.P, P_new);
+        assert_eq!(data.q, &[0.5, -1.0]);
+        assert_eq!(
+            data.A,
+            CscMatrix::from(&[[2.0, 1.0], [1.0, 0.0], [0.0, 4.0]])
+        );
+        assert_eq!(data.l, &[0.5, -1.0, -1.0]);
+        assert_eq!(data.u, &[2.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn update_matrix_entries() {
+        // Define problem data
+        let P_wrong = CscMatrix::from(&[[2.0, 1.0], [1.0, 4.0]]).into_upper_tri();
+        let A_wrong = &[[2.0, 3.0], [1.0, 0.0], [0.0, 9.0]];
+
+        let q = &[1.0, 1.0];
+        let l = &[1.0, 0.0, 0.0];
+        let u = &[1.0, 0.7, 0.7];
+
+        let settings = Settings::default().alpha(1.0).verbose(false);
+        let settings = settings.adaptive_rho(false);
+
+        let mut prob = Problem::new(&P_wrong, q, A_wrong, l, u, &settings).unwrap();
+
+        let P_idx = [
+            prob.P_entry_index(0, 0).unwrap(),
+            prob.P_entry_index(1, 1).unwrap(),
+        ];
+        assert_eq!(prob.P_entry_index(1, 0), None);
+        prob.update_P_entries(&P_idx, &[4.0, 2.0]).unwrap();
+
+        let A_idx = [
+            prob.A_entry_index(0, 0).unwrap(),
+            prob.A_entry_index(0, 1).unwrap(),
+            prob.A_entry_index(2, 1).unwrap(),
+        ];
+        assert_eq!(prob.A_entry_index(1, 1), None);
+        prob.update_A_entries(&A_idx, &[1.0, 1.0, 1.0]).unwrap();
+
+        // Results should match the `update_matrices` test
+        let result = prob.solve().unwrap();
         let x = result.solution().unwrap().x();
         let expected = &[0.2987710845986426, 0.701227995544065];
         assert_eq!(expected.len(), x.len());
-        assert!(expected.iter().zip(x).all(|(&a, &b)| (a - b).abs() < 1e-9));
+        assert!(expected.iter().zip(x).all(|(&a, &b)| (a - b).abs() < TOL));
+
+        match prob.update_A_entries(&[4], &[1.0]) {
+            Err(UpdateError::IndexOutOfBounds {
+                name: "A",
+                index: 4,
+                len: 4,
+            }) => (),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn update_matrices_structure_mismatch() {
+        let settings = Settings::default().verbose(false);
+        let mut prob = example_problem(&settings).unwrap();
+
+        // Same number of non-zero elements but a different sparsity structure
+        let A_wrong = &[[1.0, 1.0], [0.0, 1.0], [1.0, 0.0]];
+        match prob.update_A(A_wrong) {
+            Err(UpdateError::SparsityMismatch {
+                name: "A",
+                column: 0,
+            }) => (),
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let P_wrong = CscMatrix::from(&[[4.0, 0.0], [0.0, 2.0]]);
+        match prob.update_P(&P_wrong) {
+            Err(UpdateError::SparsityMismatch {
+                name: "P",
+                column: 1,
+            }) => (),
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let A_wrong = &[[1.0, 1.0], [1.0, 0.0]];
+        match prob.update_A(A_wrong) {
+            Err(UpdateError::ShapeMismatch {
+                name: "A",
+                expected: (3, 2),
+                found: (2, 2),
+            }) => (),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn update_dimension_mismatch() {
+        let settings = Settings::default().verbose(false);
+        let mut prob = example_problem(&settings).unwrap();
+
+        match prob.update_lin_cost(&[1.0, 1.0, 1.0]) {
+            Err(UpdateError::DimensionMismatch {
+                name: "q",
+                expected: 2,
+                found: 3,
+            }) => (),
+            other => panic!("unexpected result {:?}", other),
+        }
+        match prob.warm_start(&[0.0, 0.0], &[0.0]) {
+            Err(UpdateError::DimensionMismatch {
+                name: "y",
+                expected: 3,
+                found: 1,
+            }) => (),
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        // A failed update must leave the problem usable
+        prob.update_bounds(&[1.0, 0.0, 0.0], &[1.0, 0.7, 0.7])
+            .unwrap();
+        assert!(prob.solve().unwrap().x().is_some());
     }
 
     #[test]
@@ -547,14 +1683,13 @@ mod tests {
         let l = &[];
         let u = &[];
         let mut prob = Problem::new(&P, q, &A, l, u, &Settings::default()).unwrap();
-        prob.update_A(&A);
+        prob.update_A(&A).unwrap();
 
         let A = CscMatrix::from(&[[0.0, 0.0], [0.0, 0.0]]);
         assert_eq!(A.data.len(), 0);
         let l = &[0.0, 0.0];
         let u = &[1.0, 1.0];
         let mut prob = Problem::new(&P, q, &A, l, u, &Settings::default()).unwrap();
-        prob.update_A(&A);
+        prob.update_A(&A).unwrap();
     }
 }
-
@@ -1,13 +1,20 @@
 use osqp_sys as ffi;
+use std::error::Error;
+use std::fmt;
 use std::mem;
 use std::ptr;
 use std::time::Duration;
 
-use {float, Problem};
+use status::secs_to_duration;
+use {float, OsqpError, Problem, UpdateError};
+
+/// The time limit in seconds which OSQP uses to mean the time limit is disabled.
+const TIME_LIMIT_DISABLED: float = 1e10;
 
 /// The linear system solver for OSQP to use.
 #[derive(Clone, Debug, PartialEq)]
 pub enum LinsysSolver {
+    #[deprecated(note = "OSQP rejects an unknown linear system solver during setup")]
     Unknown,
     Direct,
     Indirect,
@@ -16,12 +23,49 @@ pub enum LinsysSolver {
     __Nonexhaustive,
 }
 
+/// The preconditioner used by the indirect linear system solver.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Preconditioner {
+    Disabled,
+    Diagonal,
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// The method used to choose when to adapt the ADMM step rho.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AdaptiveRho {
+    /// Rho is never adapted.
+    Disabled,
+    /// Rho is adapted every `adaptive_rho_interval` iterations.
+    Iterations,
+    /// Rho is adapted after a fraction, `adaptive_rho_fraction`, of the setup time has elapsed.
+    Time,
+    /// Rho is adapted once the KKT error has been reduced by a fraction, `adaptive_rho_fraction`,
+    /// of its value at the previous adaptation.
+    KktError,
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl From<bool> for AdaptiveRho {
+    fn from(value: bool) -> AdaptiveRho {
+        if value {
+            AdaptiveRho::Iterations
+        } else {
+            AdaptiveRho::Disabled
+        }
+    }
+}
+
 macro_rules! u32_to_osqp_int {
     ($name:ident, $value:expr) => {{
         let value = $value;
         assert!(
             value as u64 <= ffi::osqp_int::max_value() as u64,
-            "{} must be smaller than the largest isize value",
+            "{} must be smaller than the largest osqp_int value",
             stringify!($name)
         );
         value as ffi::osqp_int
@@ -34,9 +78,16 @@ macro_rules! rust_type {
     (option_u32) => (Option<u32>);
     (bool) => (bool);
     (linsys_solver) => (LinsysSolver);
+    (preconditioner) => (Preconditioner);
+    (adaptive_rho) => (impl Into<AdaptiveRho>);
     (option_duration) => (Option<Duration>);
 }
 
+macro_rules! rust_getter_type {
+    (adaptive_rho) => (AdaptiveRho);
+    ($typ:ident) => (rust_type!($typ));
+}
+
 macro_rules! convert_rust_type {
     ($name:ident, float, $value:expr) => ($value);
     ($name:ident, u32, $value:expr) => (u32_to_osqp_int!($name, $value));
@@ -50,6 +101,22 @@ macro_rules! convert_rust_type {
             LinsysSolver::__Nonexhaustive => unreachable!(),
         }
     );
+    ($name:ident, preconditioner, $value:expr) => (
+        match $value {
+            Preconditioner::Disabled => ffi::OSQP_NO_PRECONDITIONER,
+            Preconditioner::Diagonal => ffi::OSQP_DIAGONAL_PRECONDITIONER,
+            Preconditioner::__Nonexhaustive => unreachable!(),
+        }
+    );
+    ($name:ident, adaptive_rho, $value:expr) => (
+        match $value.into() {
+            AdaptiveRho::Disabled => ffi::OSQP_ADAPTIVE_RHO_UPDATE_DISABLED,
+            AdaptiveRho::Iterations => ffi::OSQP_ADAPTIVE_RHO_UPDATE_ITERATIONS,
+            AdaptiveRho::Time => ffi::OSQP_ADAPTIVE_RHO_UPDATE_TIME,
+            AdaptiveRho::KktError => ffi::OSQP_ADAPTIVE_RHO_UPDATE_KKT_ERROR,
+            AdaptiveRho::__Nonexhaustive => unreachable!(),
+        } as ffi::osqp_int
+    );
     ($name:ident, option_duration, $value:expr) => (
         $value.map(|v| {
             let mut secs = duration_to_secs(v);
@@ -59,13 +126,53 @@ macro_rules! convert_rust_type {
                 secs = 1e-12;
             }
             secs
-        }).unwrap_or(0.0)
+        }).unwrap_or(TIME_LIMIT_DISABLED)
+    );
+}
+
+macro_rules! convert_ffi_type {
+    (float, $value:expr) => ($value);
+    (u32, $value:expr) => ($value as u32);
+    (option_u32, $value:expr) => (
+        match $value {
+            0 => None,
+            value => Some(value as u32),
+        }
+    );
+    (bool, $value:expr) => ($value != 0);
+    (linsys_solver, $value:expr) => (
+        match $value {
+            ffi::OSQP_DIRECT_SOLVER => LinsysSolver::Direct,
+            ffi::OSQP_INDIRECT_SOLVER => LinsysSolver::Indirect,
+            _ => LinsysSolver::Unknown,
+        }
+    );
+    (preconditioner, $value:expr) => (
+        match $value {
+            ffi::OSQP_DIAGONAL_PRECONDITIONER => Preconditioner::Diagonal,
+            _ => Preconditioner::Disabled,
+        }
+    );
+    (adaptive_rho, $value:expr) => (
+        match $value as ffi::osqp_adaptive_rho_update_type {
+            ffi::OSQP_ADAPTIVE_RHO_UPDATE_ITERATIONS => AdaptiveRho::Iterations,
+            ffi::OSQP_ADAPTIVE_RHO_UPDATE_TIME => AdaptiveRho::Time,
+            ffi::OSQP_ADAPTIVE_RHO_UPDATE_KKT_ERROR => AdaptiveRho::KktError,
+            _ => AdaptiveRho::Disabled,
+        }
+    );
+    (option_duration, $value:expr) => (
+        match $value {
+            secs if secs > 0.0 && secs < TIME_LIMIT_DISABLED => Some(secs_to_duration(secs)),
+            _ => None,
+        }
     );
 }
 
 macro_rules! settings {
     ($problem_ty:ty, $(
-        #[$doc:meta] $name:ident: $typ:ident $([$update_name:ident, $update_ffi:ident])*,
+        #[$doc:meta] $name:ident, $getter:ident: $typ:ident
+        $([$update_name:ident $(, $update_ffi:ident)?])?,
     )*) => (
         /// The settings used when initialising a solver.
         pub struct Settings {
@@ -75,15 +182,36 @@ macro_rules! settings {
         impl Settings {
             $(
                 #[$doc]
+                #[allow(deprecated)]
                 pub fn $name(mut self, value: rust_type!($typ)) -> Settings {
                     self.inner.$name = convert_rust_type!($name, $typ, value);
                     Settings {
                         inner: self.inner
                     }
                 }
+
+                #[doc = concat!("Returns the value set by `Settings::", stringify!($name), "`.")]
+                #[allow(deprecated)]
+                pub fn $getter(&self) -> rust_getter_type!($typ) {
+                    convert_ffi_type!($typ, self.inner.$name)
+                }
             )*
         }
 
+        impl fmt::Debug for Settings {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct("Settings")
+                    $(.field(stringify!($name), &self.$getter()))*
+                    .finish()
+            }
+        }
+
+        impl PartialEq for Settings {
+            fn eq(&self, other: &Settings) -> bool {
+                true $(&& self.$getter() == other.$getter())*
+            }
+        }
+
         impl Clone for Settings {
             fn clone(&self) -> Settings {
                 unsafe {
@@ -111,19 +239,34 @@ macro_rules! settings {
 
         impl $problem_ty {
             $($(
-                #[$doc]
-                pub fn $update_name(&mut self, value: rust_type!($typ)) {
-                    unsafe {
-                        let ret = ffi::$update_ffi(
-                            self.solver,
-                            convert_rust_type!($name, $typ, value)
-                        );
-                        if ret != 0 {
-                            panic!("updating {} failed", stringify!($name));
-                        }
-                    }
+                update_setting!(#[$doc] $name, $typ, $update_name $(, $update_ffi)?);
+            )?)*
+        }
+    );
+}
+
+macro_rules! update_setting {
+    (#[$doc:meta] $name:ident, $typ:ident, $update_name:ident, $update_ffi:ident) => (
+        #[$doc]
+        pub fn $update_name(&mut self, value: rust_type!($typ)) -> Result<(), UpdateError> {
+            unsafe {
+                let ret = ffi::$update_ffi(
+                    self.solver,
+                    convert_rust_type!($name, $typ, value)
+                );
+                if ret != 0 {
+                    return Err(UpdateError::Osqp(OsqpError::from_ffi(ret)));
                 }
-            )*)*
+            }
+            Ok(())
+        }
+    );
+    (#[$doc:meta] $name:ident, $typ:ident, $update_name:ident) => (
+        #[$doc]
+        pub fn $update_name(&mut self, value: rust_type!($typ)) -> Result<(), UpdateError> {
+            let mut settings = Settings::from_problem(self);
+            settings.inner.$name = convert_rust_type!($name, $typ, value);
+            self.update_settings(&settings)
         }
     );
 }
@@ -131,32 +274,62 @@ macro_rules! settings {
 settings! {
     Problem,
 
+    #[doc = "
+    Sets the device identifier used by GPU algebra backends.
+
+    Panics on 32-bit platforms or with the `i32` feature if the value is above `i32::max_value()`.
+    "]
+    device, get_device: u32,
+
+    #[doc = "
+    Enables allocating the solution vectors during setup.
+
+    If disabled the solution, iterate and infeasibility certificates are returned as empty
+    slices.
+    "]
+    allocate_solution, get_allocate_solution: bool,
+
+    #[doc = "
+    Sets the level of detail of profiler annotations.
+
+    Panics on 32-bit platforms or with the `i32` feature if the value is above `i32::max_value()`.
+    "]
+    profiler_level, get_profiler_level: u32,
+
     #[doc = "Sets the ADMM step rho."]
-    rho: float [update_rho, osqp_update_rho],
+    rho, get_rho: float [update_rho, osqp_update_rho],
+
+    #[doc = "Enables using a different rho for equality and inequality constraints."]
+    rho_is_vec, get_rho_is_vec: bool,
 
     #[doc = "Sets the ADMM step sigma."]
-    sigma: float,
+    sigma, get_sigma: float,
 
     #[doc = "
     Sets the number of heuristic data scaling iterations.
 
     If `None` scaling is disabled.
 
-    Panics on 32-bit platforms if the value is above `i32::max_value()`.
+    Panics on 32-bit platforms or with the `i32` feature if the value is above `i32::max_value()`.
     "]
-    scaling: option_u32,
+    scaling, get_scaling: option_u32,
+
+    #[doc = "
+    Sets the method used to choose when to adapt rho.
 
-    #[doc = "Enables choosing rho adaptively."]
-    adaptive_rho: bool,
+    Passing `true` or `false` is equivalent to `AdaptiveRho::Iterations` or
+    `AdaptiveRho::Disabled`.
+    "]
+    adaptive_rho, get_adaptive_rho: adaptive_rho,
 
     #[doc = "
     Sets the number of iterations between rho adaptations.
 
     If `None` it is automatic.
 
-    Panics on 32-bit platforms if the value is above `i32::max_value()`.
+    Panics on 32-bit platforms or with the `i32` feature if the value is above `i32::max_value()`.
     "]
-    adaptive_rho_interval: option_u32,
+    adaptive_rho_interval, get_adaptive_rho_interval: option_u32,
 
     #[doc = "
     Sets the tolerance for adapting rho.
@@ -164,71 +337,262 @@ settings! {
     The new rho has to be `value` times larger or `1/value` times smaller than the current rho to
     trigger a new factorization.
     "]
-    adaptive_rho_tolerance: float,
+    adaptive_rho_tolerance, get_adaptive_rho_tolerance: float,
 
-    #[doc = "Set the interval for adapting rho as a fraction of the setup time."]
-    adaptive_rho_fraction: float,
+    #[doc = "
+    Sets the fraction used when adapting rho.
+
+    This is a fraction of the setup time for `AdaptiveRho::Time` and of the previous KKT error
+    for `AdaptiveRho::KktError`.
+    "]
+    adaptive_rho_fraction, get_adaptive_rho_fraction: float,
 
     #[doc = "
     Sets the maximum number of ADMM iterations.
 
-    Panics on 32-bit platforms if the value is above `i32::max_value()`.
+    Panics on 32-bit platforms or with the `i32` feature if the value is above `i32::max_value()`.
     "]
-    max_iter: u32,
+    max_iter, get_max_iter: u32 [update_max_iter],
 
     #[doc = "Sets the absolute convergence tolerance."]
-    eps_abs: float,
+    eps_abs, get_eps_abs: float [update_eps_abs],
 
     #[doc = "Sets the relative convergence tolerance."]
-    eps_rel: float,
+    eps_rel, get_eps_rel: float [update_eps_rel],
 
     #[doc = "Sets the primal infeasibility tolerance."]
-    eps_prim_inf: float,
+    eps_prim_inf, get_eps_prim_inf: float [update_eps_prim_inf],
 
     #[doc = "Sets the dual infeasibility tolerance."]
-    eps_dual_inf: float,
+    eps_dual_inf, get_eps_dual_inf: float [update_eps_dual_inf],
 
     #[doc = "Sets the linear solver relaxation parameter."]
-    alpha: float,
+    alpha, get_alpha: float [update_alpha],
 
     #[doc = "Sets the linear system solver to use."]
-    linsys_solver: linsys_solver,
+    linsys_solver, get_linsys_solver: linsys_solver,
+
+    #[doc = "
+    Sets the maximum number of conjugate gradient iterations per solve of the indirect linear
+    system solver.
+
+    Panics on 32-bit platforms or with the `i32` feature if the value is above `i32::max_value()`.
+    "]
+    cg_max_iter, get_cg_max_iter: u32 [update_cg_max_iter],
+
+    #[doc = "
+    Sets the number of consecutive zero conjugate gradient iterations before the tolerance is
+    halved.
+
+    Panics on 32-bit platforms or with the `i32` feature if the value is above `i32::max_value()`.
+    "]
+    cg_tol_reduction, get_cg_tol_reduction: u32 [update_cg_tol_reduction],
+
+    #[doc = "Sets the conjugate gradient tolerance as a fraction of the ADMM residuals."]
+    cg_tol_fraction, get_cg_tol_fraction: float [update_cg_tol_fraction],
+
+    #[doc = "Sets the preconditioner used by the indirect linear system solver."]
+    cg_precond, get_cg_precond: preconditioner,
 
     #[doc = "Sets the polishing regularization parameter."]
-    delta: float,
+    delta, get_delta: float [update_delta],
 
     #[doc = "Enables polishing the ADMM solution."]
-    polishing: bool,
+    polishing, get_polishing: bool [update_polishing],
 
     #[doc = "
     Sets the number of iterative refinement steps to use when polishing.
 
-    Panics on 32-bit platforms if the value is above `i32::max_value()`.
+    Panics on 32-bit platforms or with the `i32` feature if the value is above `i32::max_value()`.
     "]
-    polish_refine_iter: u32,
+    polish_refine_iter, get_polish_refine_iter: u32 [update_polish_refine_iter],
 
-    #[doc = "Enables writing progress to stdout."]
-    verbose: bool,
+    #[doc = "
+    Enables printing progress, which is forwarded to the sink set using `set_print_sink`.
+
+    Nothing is printed if printing is disabled (see the `printing` feature).
+    "]
+    verbose, get_verbose: bool [update_verbose],
 
     #[doc = "Enables scaled termination criteria."]
-    scaled_termination: bool,
+    scaled_termination, get_scaled_termination: bool [update_scaled_termination],
 
     #[doc = "
     Sets the number of ADMM iterations between termination checks.
 
     If `None` termination checking is disabled.
 
-    Panics on 32-bit platforms if the value is above `i32::max_value()`.
+    Panics on 32-bit platforms or with the `i32` feature if the value is above `i32::max_value()`.
     "]
-    check_termination: option_u32,
+    check_termination, get_check_termination: option_u32 [update_check_termination],
+
+    #[doc = "Enables the duality gap termination criterion."]
+    check_dualgap, get_check_dualgap: bool [update_check_dualgap],
 
     #[doc = "Enables warm starting the primal and dual variables from the previous solution."]
-    warm_starting: bool,
+    warm_starting, get_warm_starting: bool [update_warm_starting],
 
-    #[doc = "Sets the solve time limit."]
-    time_limit: option_duration,
+    #[doc = "
+    Sets the solve time limit, or disables it if `None`.
+
+    The limit is only enforced if profiling is enabled (see the `profiling` feature).
+    "]
+    time_limit, get_time_limit: option_duration [update_time_limit],
 }
 
+impl Settings {
+    /// Checks that the value of each setting is accepted by OSQP.
+    ///
+    /// This is called by `Problem::new` and `Problem::update_settings` and applies the same rules
+    /// as OSQP.
+    pub fn validate(&self) -> Result<(), SettingsError> {
+        self.check(true)
+    }
+
+    /// Checks the settings which can be changed after setup.
+    pub(crate) fn validate_update(&self) -> Result<(), SettingsError> {
+        self.check(false)
+    }
+
+    // Comparisons are negated so that NaN values are rejected
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
+    fn check(&self, from_setup: bool) -> Result<(), SettingsError> {
+        let s = &self.inner;
+
+        macro_rules! ensure {
+            ($field:ident, $valid:expr, $reason:expr) => {
+                if !$valid {
+                    return Err(SettingsError {
+                        field: stringify!($field),
+                        value: format!("{:?}", s.$field),
+                        reason: $reason,
+                    });
+                }
+            };
+        }
+
+        ensure!(rho, s.rho > 0.0, "must be positive");
+        if from_setup {
+            ensure!(
+                linsys_solver,
+                s.linsys_solver == ffi::OSQP_DIRECT_SOLVER
+                    || s.linsys_solver == ffi::OSQP_INDIRECT_SOLVER,
+                "must be Direct or Indirect"
+            );
+            ensure!(sigma, s.sigma > 0.0, "must be positive");
+            ensure!(
+                adaptive_rho,
+                s.adaptive_rho >= 0
+                    && (s.adaptive_rho as ffi::osqp_adaptive_rho_update_type)
+                        < ffi::OSQP_ADAPTIVE_RHO_UPDATE_LAST_VALUE,
+                "not a recognised adaptive rho mode"
+            );
+            ensure!(
+                adaptive_rho_fraction,
+                s.adaptive_rho_fraction > 0.0,
+                "must be positive"
+            );
+            ensure!(
+                adaptive_rho_tolerance,
+                s.adaptive_rho_tolerance >= 1.0,
+                "must be greater than or equal to 1"
+            );
+        }
+        ensure!(max_iter, s.max_iter > 0, "must be positive");
+        ensure!(eps_abs, s.eps_abs >= 0.0, "must be non-negative");
+        ensure!(eps_rel, s.eps_rel >= 0.0, "must be non-negative");
+        ensure!(
+            eps_rel,
+            s.eps_abs != 0.0 || s.eps_rel != 0.0,
+            "must be positive if eps_abs is zero"
+        );
+        ensure!(eps_prim_inf, s.eps_prim_inf >= 0.0, "must be non-negative");
+        ensure!(eps_dual_inf, s.eps_dual_inf >= 0.0, "must be non-negative");
+        ensure!(
+            alpha,
+            s.alpha > 0.0 && s.alpha < 2.0,
+            "must be between 0 and 2 exclusive"
+        );
+        ensure!(cg_max_iter, s.cg_max_iter > 0, "must be positive");
+        ensure!(cg_tol_reduction, s.cg_tol_reduction > 0, "must be positive");
+        ensure!(
+            cg_tol_fraction,
+            s.cg_tol_fraction > 0.0 && s.cg_tol_fraction < 1.0,
+            "must be between 0 and 1 exclusive"
+        );
+        ensure!(delta, s.delta > 0.0, "must be positive");
+        ensure!(time_limit, s.time_limit >= 0.0, "must be non-negative");
+        Ok(())
+    }
+
+    /// Returns the name of the first setting which can only be changed during setup and differs
+    /// between `self` and `other`.
+    pub(crate) fn setup_only_difference(&self, other: &Settings) -> Option<&'static str> {
+        macro_rules! compare {
+            ($($name:ident, $getter:ident;)*) => {
+                $(
+                    if self.$getter() != other.$getter() {
+                        return Some(stringify!($name));
+                    }
+                )*
+            };
+        }
+
+        // OSQP chooses `adaptive_rho_interval` during setup if it is zero so an interval of zero
+        // in `self` keeps the value chosen
+        if self.get_adaptive_rho_interval().is_some()
+            && self.get_adaptive_rho_interval() != other.get_adaptive_rho_interval()
+        {
+            return Some("adaptive_rho_interval");
+        }
+        compare! {
+            device, get_device;
+            allocate_solution, get_allocate_solution;
+            profiler_level, get_profiler_level;
+            rho_is_vec, get_rho_is_vec;
+            sigma, get_sigma;
+            scaling, get_scaling;
+            adaptive_rho, get_adaptive_rho;
+            adaptive_rho_tolerance, get_adaptive_rho_tolerance;
+            adaptive_rho_fraction, get_adaptive_rho_fraction;
+            linsys_solver, get_linsys_solver;
+            cg_precond, get_cg_precond;
+        }
+        None
+    }
+
+    /// Returns the settings currently used by the solver of `problem`.
+    ///
+    /// This includes changes made after setup and any values chosen by OSQP during setup, such as
+    /// an automatic `adaptive_rho_interval`.
+    pub fn from_problem(problem: &Problem) -> Settings {
+        unsafe {
+            Settings {
+                inner: ptr::read((*problem.solver).settings),
+            }
+        }
+    }
+}
+
+/// An invalid setting reported by `Settings::validate`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SettingsError {
+    /// The name of the setting.
+    pub field: &'static str,
+    /// The invalid value as stored by OSQP.
+    pub value: String,
+    /// The requirement the value does not satisfy.
+    pub reason: &'static str,
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} {}: {}", self.field, self.value, self.reason)
+    }
+}
+
+impl Error for SettingsError {}
+
 fn duration_to_secs(dur: Duration) -> float {
     dur.as_secs() as float + dur.subsec_nanos() as float * 1e-9
 }
@@ -236,8 +600,10 @@ fn duration_to_secs(dur: Duration) -> float {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tests::example_problem;
+    use SetupError;
 
-    #[cfg(target_pointer_width = "32")]
+    #[cfg(any(target_pointer_width = "32", feature = "i32"))]
     #[test]
     #[should_panic]
     fn large_u32_settings_value_panics_on_32_bit() {
@@ -245,10 +611,169 @@ mod tests {
         Settings::default().polish_refine_iter(3_000_000_000);
     }
 
+    #[test]
+    fn adaptive_rho_modes() {
+        let settings = Settings::default().adaptive_rho(AdaptiveRho::KktError);
+        assert_eq!(
+            settings.inner.adaptive_rho,
+            ffi::OSQP_ADAPTIVE_RHO_UPDATE_KKT_ERROR as ffi::osqp_int
+        );
+        let settings = settings.adaptive_rho(false);
+        assert_eq!(
+            settings.inner.adaptive_rho,
+            ffi::OSQP_ADAPTIVE_RHO_UPDATE_DISABLED as ffi::osqp_int
+        );
+        let settings = settings.adaptive_rho(true);
+        assert_eq!(
+            settings.inner.adaptive_rho,
+            ffi::OSQP_ADAPTIVE_RHO_UPDATE_ITERATIONS as ffi::osqp_int
+        );
+    }
+
+    #[test]
+    fn indirect_solver_settings() {
+        let settings = Settings::default()
+            .linsys_solver(LinsysSolver::Indirect)
+            .cg_max_iter(50)
+            .cg_tol_reduction(5)
+            .cg_tol_fraction(0.1)
+            .cg_precond(Preconditioner::Disabled);
+        assert_eq!(settings.inner.cg_max_iter, 50);
+        assert_eq!(settings.inner.cg_tol_reduction, 5);
+        assert_eq!(settings.inner.cg_tol_fraction, 0.1);
+        assert_eq!(settings.inner.cg_precond, ffi::OSQP_NO_PRECONDITIONER);
+    }
+
+    #[test]
+    fn getters_round_trip() {
+        let settings = Settings::default()
+            .rho(0.2)
+            .scaling(None)
+            .max_iter(100)
+            .adaptive_rho(AdaptiveRho::Time)
+            .linsys_solver(LinsysSolver::Indirect)
+            .cg_precond(Preconditioner::Diagonal)
+            .polishing(true)
+            .check_termination(Some(5))
+            .time_limit(Some(Duration::from_millis(1500)));
+        assert_eq!(settings.get_rho(), 0.2);
+        assert_eq!(settings.get_scaling(), None);
+        assert_eq!(settings.get_max_iter(), 100);
+        assert_eq!(settings.get_adaptive_rho(), AdaptiveRho::Time);
+        assert_eq!(settings.get_linsys_solver(), LinsysSolver::Indirect);
+        assert_eq!(settings.get_cg_precond(), Preconditioner::Diagonal);
+        assert!(settings.get_polishing());
+        assert_eq!(settings.get_check_termination(), Some(5));
+        assert_eq!(settings.get_time_limit(), Some(Duration::from_millis(1500)));
+        assert_eq!(settings.clone().time_limit(None).get_time_limit(), None);
+        assert_eq!(Settings::default().get_time_limit(), None);
+
+        assert_eq!(settings, settings.clone());
+        assert!(settings != settings.clone().max_iter(101));
+        assert!(format!("{:?}", settings).contains("max_iter: 100"));
+    }
+
+    #[test]
+    fn validate_settings() {
+        assert_eq!(Settings::default().validate(), Ok(()));
+
+        let err = Settings::default().eps_abs(-1.0).validate().unwrap_err();
+        assert_eq!(err.field, "eps_abs");
+        assert_eq!(err.value, "-1.0");
+
+        assert_eq!(
+            Settings::default().alpha(2.0).validate().unwrap_err().field,
+            "alpha"
+        );
+        assert_eq!(
+            Settings::default().sigma(0.0).validate().unwrap_err().field,
+            "sigma"
+        );
+        assert_eq!(
+            Settings::default()
+                .eps_abs(0.0)
+                .eps_rel(0.0)
+                .validate()
+                .unwrap_err()
+                .field,
+            "eps_rel"
+        );
+
+        // Setup-only settings are ignored when updating
+        assert_eq!(Settings::default().sigma(0.0).validate_update(), Ok(()));
+        assert!(Settings::default().max_iter(0).validate_update().is_err());
+    }
+
     #[test]
     fn duration_to_secs_examples() {
         assert_eq!(duration_to_secs(Duration::new(2, 0)), 2.0);
         assert_eq!(duration_to_secs(Duration::new(8, 100_000_000)), 8.1);
         assert_eq!(duration_to_secs(Duration::new(0, 10_000_000)), 0.01);
     }
+
+    #[test]
+    fn settings_from_problem() {
+        let settings = Settings::default().verbose(false).eps_abs(1e-5);
+        let mut prob = example_problem(&settings).unwrap();
+        assert_eq!(Settings::from_problem(&prob).get_eps_abs(), 1e-5);
+
+        prob.update_settings(&settings.clone().max_iter(321))
+            .unwrap();
+        let current = Settings::from_problem(&prob);
+        assert_eq!(current.get_max_iter(), 321);
+        assert!(!current.get_verbose());
+    }
+
+    #[test]
+    fn invalid_settings() {
+        let settings = Settings::default().verbose(false);
+        match example_problem(&settings.clone().sigma(-1.0)) {
+            Err(SetupError::InvalidSetting(err)) => assert_eq!(err.field, "sigma"),
+            _ => panic!("expected InvalidSetting"),
+        }
+
+        let mut prob = example_problem(&settings).unwrap();
+        match prob.update_settings(&settings.clone().alpha(0.0)) {
+            Err(UpdateError::InvalidSetting(err)) => assert_eq!(err.field, "alpha"),
+            other => panic!("expected InvalidSetting but found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn update_individual_settings() {
+        let settings = Settings::default().verbose(false).eps_abs(1e-5);
+        let mut prob = example_problem(&settings).unwrap();
+
+        prob.update_max_iter(10).unwrap();
+        prob.update_time_limit(Some(Duration::from_secs(2)))
+            .unwrap();
+        prob.update_polishing(true).unwrap();
+        let current = Settings::from_problem(&prob);
+        assert_eq!(current.get_max_iter(), 10);
+        assert_eq!(current.get_time_limit(), Some(Duration::from_secs(2)));
+        assert!(current.get_polishing());
+        // Other settings are unchanged
+        assert_eq!(current.get_eps_abs(), 1e-5);
+
+        match prob.update_eps_abs(-1.0) {
+            Err(UpdateError::InvalidSetting(err)) => assert_eq!(err.field, "eps_abs"),
+            other => panic!("expected InvalidSetting but found {:?}", other),
+        }
+        match prob.update_settings(&current.clone().sigma(1.0)) {
+            Err(UpdateError::SetupOnlySetting("sigma")) => (),
+            other => panic!("expected SetupOnlySetting but found {:?}", other),
+        }
+        match prob.update_settings(&current.clone().scaling(None)) {
+            Err(UpdateError::SetupOnlySetting("scaling")) => (),
+            other => panic!("expected SetupOnlySetting but found {:?}", other),
+        }
+        let interval = current.get_adaptive_rho_interval().unwrap_or(0) + 1;
+        match prob.update_settings(&current.clone().adaptive_rho_interval(Some(interval))) {
+            Err(UpdateError::SetupOnlySetting("adaptive_rho_interval")) => (),
+            other => panic!("expected SetupOnlySetting but found {:?}", other),
+        }
+
+        prob.update_settings(&current.clone().rho(0.5)).unwrap();
+        assert_eq!(Settings::from_problem(&prob).get_rho(), 0.5);
+    }
 }
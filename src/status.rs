@@ -3,10 +3,12 @@ use std::fmt;
 use std::slice;
 use std::time::Duration;
 
-use {float, Problem};
+use {float, int_to_i64, ConvergenceHistory, Problem};
 use osqp_sys::osqp_status_type;
 
 /// The result of solving a problem.
+///
+/// The timing accessors return `None` if profiling is disabled (see the `profiling` feature).
 #[derive(Clone, Debug)]
 pub enum Status<'a> {
     Solved(Solution<'a>),
@@ -18,6 +20,13 @@ pub enum Status<'a> {
     DualInfeasible(DualInfeasibilityCertificate<'a>),
     DualInfeasibleInaccurate(DualInfeasibilityCertificate<'a>),
     NonConvex(Failure<'a>),
+    /// The solve was interrupted before it terminated. The iterate at the time of the
+    /// interruption is available.
+    Interrupted(Solution<'a>),
+    /// The problem has not been solved. The current iterate is available.
+    Unsolved(Solution<'a>),
+    /// The solver returned a status code not recognised by this version of the wrapper.
+    Unknown(i64, Failure<'a>),
     // Prevent exhaustive enum matching
     #[doc(hidden)]
     __Nonexhaustive,
@@ -47,12 +56,58 @@ pub struct Failure<'a> {
     prob: &'a Problem,
 }
 
+/// How closely a solution returned by `Status::iterate` satisfies the termination criteria.
+#[derive(Copy, Clone, Debug, Hash, PartialEq)]
+#[allow(clippy::manual_non_exhaustive)]
+pub enum SolutionQuality {
+    /// The solution satisfies the requested tolerances (`Status::Solved`).
+    Exact,
+    /// The solution satisfies relaxed tolerances (`Status::SolvedInaccurate`).
+    Inaccurate,
+    /// The solver stopped before converging (`Status::MaxIterationsReached`,
+    /// `Status::TimeLimitReached` or `Status::Interrupted`).
+    Truncated,
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// A copy of the information reported by the solver at the end of a solve.
+///
+/// Unlike `Status` it does not borrow the `Problem` so remains available after the problem is
+/// updated or solved again. The timing accessors return `None` if profiling is disabled (see
+/// the `profiling` feature).
+#[derive(Copy, Clone, PartialEq)]
+pub struct SolveInfo {
+    status: [u8; 32],
+    status_val: i64,
+    polish_status: PolishStatus,
+    obj_val: float,
+    dual_obj_val: float,
+    pri_res: float,
+    dua_res: float,
+    duality_gap: float,
+    iter: u32,
+    rho_updates: u32,
+    rho_estimate: float,
+    setup_time: Option<Duration>,
+    solve_time: Option<Duration>,
+    update_time: Option<Duration>,
+    polish_time: Option<Duration>,
+    run_time: Option<Duration>,
+    primdual_int: float,
+    rel_kkt_error: float,
+}
+
 /// The status of the polish operation.
 #[derive(Copy, Clone, Debug, Hash, PartialEq)]
+#[allow(clippy::manual_non_exhaustive)]
 pub enum PolishStatus {
     Successful,
     Unsuccessful,
     Unperformed,
+    /// OSQP returned a polish status code not recognised by this version of the wrapper.
+    Unknown(i64),
     // Prevent exhaustive enum matching
     #[doc(hidden)]
     __Nonexhaustive,
@@ -60,8 +115,12 @@ pub enum PolishStatus {
 
 impl<'a> Status<'a> {
     pub(crate) fn from_problem(prob: &'a Problem) -> Status<'a> {
+        if prob.steps_interrupted {
+            return Status::Interrupted(Solution { prob });
+        }
         unsafe {
-            match (*(*prob.solver).info).status_val as osqp_status_type {
+            let status_val = (*(*prob.solver).info).status_val;
+            match status_val as osqp_status_type {
                 ffi::OSQP_SOLVED => Status::Solved(Solution { prob }),
                 ffi::OSQP_SOLVED_INACCURATE => Status::SolvedInaccurate(Solution { prob }),
                 ffi::OSQP_MAX_ITER_REACHED => Status::MaxIterationsReached(Solution { prob }),
@@ -79,7 +138,9 @@ impl<'a> Status<'a> {
                     Status::DualInfeasibleInaccurate(DualInfeasibilityCertificate { prob })
                 }
                 ffi::OSQP_NON_CVX => Status::NonConvex(Failure { prob }),
-                _ => unreachable!(),
+                ffi::OSQP_SIGINT => Status::Interrupted(Solution { prob }),
+                ffi::OSQP_UNSOLVED => Status::Unsolved(Solution { prob }),
+                _ => Status::Unknown(int_to_i64(status_val), Failure { prob }),
             }
         }
     }
@@ -97,42 +158,70 @@ impl<'a> Status<'a> {
         }
     }
 
+    /// Returns the last iterate of the solver, whether or not it converged, along with an
+    /// indication of its quality.
+    ///
+    /// Unlike `Status::solution` this also returns the iterate when the problem was solved
+    /// inaccurately or the solver stopped early. The residuals at termination, available from
+    /// `Solution::pri_res` and `Solution::dua_res`, can be used to decide whether the iterate is
+    /// usable. Returns `None` if the problem is infeasible, non-convex or unsolved.
+    pub fn iterate(&self) -> Option<(Solution<'a>, SolutionQuality)> {
+        match *self {
+            Status::Solved(ref solution) => Some((solution.clone(), SolutionQuality::Exact)),
+            Status::SolvedInaccurate(ref solution) => {
+                Some((solution.clone(), SolutionQuality::Inaccurate))
+            }
+            Status::MaxIterationsReached(ref solution)
+            | Status::TimeLimitReached(ref solution)
+            | Status::Interrupted(ref solution) => {
+                Some((solution.clone(), SolutionQuality::Truncated))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns a copy of all the information reported by the solver.
+    pub fn info(&self) -> SolveInfo {
+        let prob = self.prob();
+        let info = unsafe { SolveInfo::from_ffi(&*(*prob.solver).info) };
+        match prob.steps_info {
+            Some(ref earlier) => earlier.followed_by(&info),
+            None => info,
+        }
+    }
+
     /// Returns the number of iterations taken by the solver.
     pub fn iter(&self) -> u32 {
-        unsafe {
-            // cast safe as more than 2 billion iterations would be unreasonable
-            (*(*self.prob().solver).info).iter as u32
-        }
+        self.info().iter()
     }
 
     /// Returns the time taken for the setup phase.
-    pub fn setup_time(&self) -> Duration {
-        unsafe { secs_to_duration((*(*self.prob().solver).info).setup_time) }
+    ///
+    /// For a solve run in steps this is the time reported by the first step.
+    pub fn setup_time(&self) -> Option<Duration> {
+        self.info().setup_time()
     }
 
     /// Returns the time taken for the solve phase.
-    pub fn solve_time(&self) -> Duration {
-        unsafe { secs_to_duration((*(*self.prob().solver).info).solve_time) }
+    pub fn solve_time(&self) -> Option<Duration> {
+        self.info().solve_time()
     }
 
     /// Returns the time taken for the polish phase.
-    pub fn polish_time(&self) -> Duration {
-        unsafe { secs_to_duration((*(*self.prob().solver).info).polish_time) }
+    pub fn polish_time(&self) -> Option<Duration> {
+        self.info().polish_time()
     }
 
     /// Returns the total time taken by the solver.
     ///
     /// This includes the time taken for the setup phase on the first solve.
-    pub fn run_time(&self) -> Duration {
-        unsafe { secs_to_duration((*(*self.prob().solver).info).run_time) }
+    pub fn run_time(&self) -> Option<Duration> {
+        self.info().run_time()
     }
 
     /// Returns the number of rho updates.
     pub fn rho_updates(&self) -> u32 {
-        unsafe {
-            // cast safe as more than 2 billion updates would be unreasonable
-            (*(*self.prob().solver).info).rho_updates as u32
-        }
+        self.info().rho_updates()
     }
 
     /// Returns the current best estimate of rho.
@@ -140,19 +229,76 @@ impl<'a> Status<'a> {
         unsafe { (*(*self.prob().solver).info).rho_estimate }
     }
 
+    /// Returns the convergence history of the solve if recording was enabled using
+    /// `Problem::record_history`.
+    pub fn history(&self) -> Option<&'a ConvergenceHistory> {
+        self.prob().history.as_ref()
+    }
+
+    /// Returns a copy of the status which does not borrow the `Problem`.
+    ///
+    /// The primal and dual variables, any infeasibility certificate, the solver information and
+    /// any convergence history are copied.
+    pub fn to_owned(&self) -> OwnedStatus {
+        let info = self.info();
+        let history = self.history();
+        let solution = |s: &Solution| OwnedSolution {
+            x: s.x().to_vec(),
+            y: s.y().to_vec(),
+            info,
+            history: history.cloned(),
+        };
+        let prim_cert = |c: &PrimalInfeasibilityCertificate| OwnedPrimalInfeasibilityCertificate {
+            delta_y: c.delta_y().to_vec(),
+            info,
+            history: history.cloned(),
+        };
+        let dual_cert = |c: &DualInfeasibilityCertificate| OwnedDualInfeasibilityCertificate {
+            delta_x: c.delta_x().to_vec(),
+            info,
+            history: history.cloned(),
+        };
+        let failure = OwnedFailure {
+            info,
+            history: history.cloned(),
+        };
+
+        match *self {
+            Status::Solved(ref s) => OwnedStatus::Solved(solution(s)),
+            Status::SolvedInaccurate(ref s) => OwnedStatus::SolvedInaccurate(solution(s)),
+            Status::MaxIterationsReached(ref s) => OwnedStatus::MaxIterationsReached(solution(s)),
+            Status::TimeLimitReached(ref s) => OwnedStatus::TimeLimitReached(solution(s)),
+            Status::PrimalInfeasible(ref c) => OwnedStatus::PrimalInfeasible(prim_cert(c)),
+            Status::PrimalInfeasibleInaccurate(ref c) => {
+                OwnedStatus::PrimalInfeasibleInaccurate(prim_cert(c))
+            }
+            Status::DualInfeasible(ref c) => OwnedStatus::DualInfeasible(dual_cert(c)),
+            Status::DualInfeasibleInaccurate(ref c) => {
+                OwnedStatus::DualInfeasibleInaccurate(dual_cert(c))
+            }
+            Status::NonConvex(_) => OwnedStatus::NonConvex(failure),
+            Status::Interrupted(ref s) => OwnedStatus::Interrupted(solution(s)),
+            Status::Unsolved(ref s) => OwnedStatus::Unsolved(solution(s)),
+            Status::Unknown(code, _) => OwnedStatus::Unknown(code, failure),
+            Status::__Nonexhaustive => unreachable!(),
+        }
+    }
+
     fn prob(&self) -> &'a Problem {
         match *self {
             Status::Solved(ref solution)
             | Status::SolvedInaccurate(ref solution)
             | Status::MaxIterationsReached(ref solution)
-            | Status::TimeLimitReached(ref solution) => solution.prob,
+            | Status::TimeLimitReached(ref solution)
+            | Status::Interrupted(ref solution)
+            | Status::Unsolved(ref solution) => solution.prob,
             Status::PrimalInfeasible(ref cert) | Status::PrimalInfeasibleInaccurate(ref cert) => {
                 cert.prob
             }
             Status::DualInfeasible(ref cert) | Status::DualInfeasibleInaccurate(ref cert) => {
                 cert.prob
             }
-            Status::NonConvex(ref failure) => failure.prob,
+            Status::NonConvex(ref failure) | Status::Unknown(_, ref failure) => failure.prob,
             Status::__Nonexhaustive => unreachable!(),
         }
     }
@@ -161,26 +307,19 @@ impl<'a> Status<'a> {
 impl<'a> Solution<'a> {
     /// Returns the primal variables at the solution.
     pub fn x(&self) -> &'a [float] {
-        unsafe { slice::from_raw_parts((*(*self.prob.solver).solution).x, self.prob.n) }
+        unsafe { solution_vec(self.prob, |s| s.x, self.prob.n) }
     }
 
     /// Returns the dual variables at the solution.
     ///
     /// These are the Lagrange multipliers of the constraints `l <= Ax <= u`.
     pub fn y(&self) -> &'a [float] {
-        unsafe { slice::from_raw_parts((*(*self.prob.solver).solution).y, self.prob.m) }
+        unsafe { solution_vec(self.prob, |s| s.y, self.prob.m) }
     }
 
     /// Returns the status of the polish operation.
     pub fn polish_status(&self) -> PolishStatus {
-        unsafe {
-            match (*(*self.prob.solver).info).status_polish {
-                1 => PolishStatus::Successful,
-                -1 => PolishStatus::Unsuccessful,
-                0 => PolishStatus::Unperformed,
-                _ => unreachable!(),
-            }
-        }
+        unsafe { PolishStatus::from_ffi((*(*self.prob.solver).info).status_polish) }
     }
 
     /// Returns the primal objective value.
@@ -212,6 +351,192 @@ impl<'a> fmt::Debug for Solution<'a> {
     }
 }
 
+impl SolveInfo {
+    pub(crate) fn from_ffi(info: &ffi::OSQPInfo) -> SolveInfo {
+        let mut status = [0; 32];
+        for (dst, &src) in status.iter_mut().zip(info.status.iter()) {
+            *dst = src as u8;
+        }
+
+        SolveInfo {
+            status,
+            status_val: int_to_i64(info.status_val),
+            polish_status: PolishStatus::from_ffi(info.status_polish),
+            obj_val: info.obj_val,
+            dual_obj_val: info.dual_obj_val,
+            pri_res: info.prim_res,
+            dua_res: info.dual_res,
+            duality_gap: info.duality_gap,
+            // casts safe as more than 2 billion iterations or updates would be unreasonable
+            iter: info.iter as u32,
+            rho_updates: info.rho_updates as u32,
+            rho_estimate: info.rho_estimate,
+            setup_time: profiled_duration(info.setup_time),
+            solve_time: profiled_duration(info.solve_time),
+            update_time: profiled_duration(info.update_time),
+            polish_time: profiled_duration(info.polish_time),
+            run_time: profiled_duration(info.run_time),
+            primdual_int: info.primdual_int,
+            rel_kkt_error: info.rel_kkt_error,
+        }
+    }
+
+    /// Combines the information of two consecutive steps of `Problem::solve_steps`.
+    ///
+    /// Iterations, rho updates and solve and polish times are summed. The setup and update times
+    /// are those measured before the first step, and everything else is taken from `next`.
+    pub(crate) fn followed_by(&self, next: &SolveInfo) -> SolveInfo {
+        SolveInfo {
+            iter: self.iter + next.iter,
+            rho_updates: self.rho_updates + next.rho_updates,
+            setup_time: self.setup_time,
+            solve_time: add_durations(self.solve_time, next.solve_time),
+            update_time: self.update_time,
+            polish_time: add_durations(self.polish_time, next.polish_time),
+            run_time: add_durations(
+                self.run_time,
+                add_durations(next.solve_time, next.polish_time),
+            ),
+            ..*next
+        }
+    }
+
+    /// Returns the status reported by OSQP as a string, e.g. `"solved"`.
+    pub fn status(&self) -> &str {
+        let len = self
+            .status
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(self.status.len());
+        ::std::str::from_utf8(&self.status[..len]).unwrap_or("")
+    }
+
+    /// Returns the status code reported by OSQP.
+    pub fn status_val(&self) -> i64 {
+        self.status_val
+    }
+
+    /// Returns the status of the polish operation.
+    pub fn polish_status(&self) -> PolishStatus {
+        self.polish_status
+    }
+
+    /// Returns the primal objective value.
+    pub fn obj_val(&self) -> float {
+        self.obj_val
+    }
+
+    /// Returns the dual objective value.
+    pub fn dual_obj_val(&self) -> float {
+        self.dual_obj_val
+    }
+
+    /// Returns the norm of primal residual.
+    pub fn pri_res(&self) -> float {
+        self.pri_res
+    }
+
+    /// Returns the norm of dual residual.
+    pub fn dua_res(&self) -> float {
+        self.dua_res
+    }
+
+    /// Returns the duality gap, i.e. the primal objective value minus the dual objective value.
+    pub fn duality_gap(&self) -> float {
+        self.duality_gap
+    }
+
+    /// Returns the number of iterations taken by the solver.
+    pub fn iter(&self) -> u32 {
+        self.iter
+    }
+
+    /// Returns the number of rho updates.
+    pub fn rho_updates(&self) -> u32 {
+        self.rho_updates
+    }
+
+    /// Returns the best estimate of rho.
+    pub fn rho_estimate(&self) -> float {
+        self.rho_estimate
+    }
+
+    /// Returns the time taken for the setup phase.
+    pub fn setup_time(&self) -> Option<Duration> {
+        self.setup_time
+    }
+
+    /// Returns the time taken for the solve phase.
+    pub fn solve_time(&self) -> Option<Duration> {
+        self.solve_time
+    }
+
+    /// Returns the time taken to update the problem data before the solve.
+    ///
+    /// For a solve run in steps this is the time reported by the first step.
+    pub fn update_time(&self) -> Option<Duration> {
+        self.update_time
+    }
+
+    /// Returns the time taken for the polish phase.
+    pub fn polish_time(&self) -> Option<Duration> {
+        self.polish_time
+    }
+
+    /// Returns the total time taken by the solver.
+    ///
+    /// This includes the time taken for the setup phase on the first solve.
+    pub fn run_time(&self) -> Option<Duration> {
+        self.run_time
+    }
+
+    /// Returns the integral of the duality gap over time.
+    pub fn primdual_int(&self) -> float {
+        self.primdual_int
+    }
+
+    /// Returns the relative KKT error.
+    pub fn rel_kkt_error(&self) -> float {
+        self.rel_kkt_error
+    }
+}
+
+impl fmt::Debug for SolveInfo {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("SolveInfo")
+            .field("status", &self.status())
+            .field("status_val", &self.status_val)
+            .field("polish_status", &self.polish_status)
+            .field("obj_val", &self.obj_val)
+            .field("dual_obj_val", &self.dual_obj_val)
+            .field("pri_res", &self.pri_res)
+            .field("dua_res", &self.dua_res)
+            .field("duality_gap", &self.duality_gap)
+            .field("iter", &self.iter)
+            .field("rho_updates", &self.rho_updates)
+            .field("rho_estimate", &self.rho_estimate)
+            .field("setup_time", &self.setup_time)
+            .field("solve_time", &self.solve_time)
+            .field("update_time", &self.update_time)
+            .field("polish_time", &self.polish_time)
+            .field("run_time", &self.run_time)
+            .field("primdual_int", &self.primdual_int)
+            .field("rel_kkt_error", &self.rel_kkt_error)
+            .finish()
+    }
+}
+
+impl PolishStatus {
+    fn from_ffi(status_polish: ffi::osqp_int) -> PolishStatus {
+        match status_polish {
+            1 => PolishStatus::Successful,
+            -1 => PolishStatus::Unsuccessful,
+            0 => PolishStatus::Unperformed,
+            other => PolishStatus::Unknown(int_to_i64(other)),
+        }
+    }
+}
+
 impl<'a> PrimalInfeasibilityCertificate<'a> {
     /// Returns the certificate of primal infeasibility.
     ///
@@ -219,7 +544,7 @@ impl<'a> PrimalInfeasibilityCertificate<'a> {
     /// multipliers for convex
     /// optimization](http://www.optimization-online.org/DB_HTML/2017/06/6058.html).
     pub fn delta_y(&self) -> &'a [float] {
-        unsafe { slice::from_raw_parts((*(*self.prob.solver).solution).prim_inf_cert, self.prob.m) }
+        unsafe { solution_vec(self.prob, |s| s.prim_inf_cert, self.prob.m) }
     }
 }
 
@@ -238,7 +563,7 @@ impl<'a> DualInfeasibilityCertificate<'a> {
     /// multipliers for convex
     /// optimization](http://www.optimization-online.org/DB_HTML/2017/06/6058.html).
     pub fn delta_x(&self) -> &'a [float] {
-        unsafe { slice::from_raw_parts((*(*self.prob.solver).solution).dual_inf_cert, self.prob.n) }
+        unsafe { solution_vec(self.prob, |s| s.dual_inf_cert, self.prob.n) }
     }
 }
 
@@ -256,8 +581,344 @@ impl<'a> fmt::Debug for Failure<'a> {
     }
 }
 
-fn secs_to_duration(secs: float) -> Duration {
+/// An owned copy of the result of solving a problem.
+///
+/// Created using `Status::to_owned` or `Problem::solve_owned`. Unlike `Status` it does not borrow
+/// the `Problem` so can be kept while the problem is updated or sent to another thread.
+#[derive(Clone, Debug)]
+pub enum OwnedStatus {
+    Solved(OwnedSolution),
+    SolvedInaccurate(OwnedSolution),
+    MaxIterationsReached(OwnedSolution),
+    TimeLimitReached(OwnedSolution),
+    PrimalInfeasible(OwnedPrimalInfeasibilityCertificate),
+    PrimalInfeasibleInaccurate(OwnedPrimalInfeasibilityCertificate),
+    DualInfeasible(OwnedDualInfeasibilityCertificate),
+    DualInfeasibleInaccurate(OwnedDualInfeasibilityCertificate),
+    NonConvex(OwnedFailure),
+    Interrupted(OwnedSolution),
+    Unsolved(OwnedSolution),
+    Unknown(i64, OwnedFailure),
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// An owned copy of a `Solution`.
+#[derive(Clone, Debug)]
+pub struct OwnedSolution {
+    x: Vec<float>,
+    y: Vec<float>,
+    info: SolveInfo,
+    history: Option<ConvergenceHistory>,
+}
+
+/// An owned copy of a `PrimalInfeasibilityCertificate`.
+#[derive(Clone, Debug)]
+pub struct OwnedPrimalInfeasibilityCertificate {
+    delta_y: Vec<float>,
+    info: SolveInfo,
+    history: Option<ConvergenceHistory>,
+}
+
+/// An owned copy of a `DualInfeasibilityCertificate`.
+#[derive(Clone, Debug)]
+pub struct OwnedDualInfeasibilityCertificate {
+    delta_x: Vec<float>,
+    info: SolveInfo,
+    history: Option<ConvergenceHistory>,
+}
+
+/// An owned copy of a `Failure`.
+#[derive(Clone, Debug)]
+pub struct OwnedFailure {
+    info: SolveInfo,
+    history: Option<ConvergenceHistory>,
+}
+
+impl OwnedStatus {
+    /// Returns the primal variables at the solution if the problem is `Solved`.
+    pub fn x(&self) -> Option<&[float]> {
+        self.solution().map(|s| s.x())
+    }
+
+    /// Returns the solution if the problem is `Solved`.
+    pub fn solution(&self) -> Option<&OwnedSolution> {
+        match *self {
+            OwnedStatus::Solved(ref solution) => Some(solution),
+            _ => None,
+        }
+    }
+
+    /// Returns the last iterate of the solver along with an indication of its quality.
+    ///
+    /// See `Status::iterate`.
+    pub fn iterate(&self) -> Option<(&OwnedSolution, SolutionQuality)> {
+        match *self {
+            OwnedStatus::Solved(ref solution) => Some((solution, SolutionQuality::Exact)),
+            OwnedStatus::SolvedInaccurate(ref solution) => {
+                Some((solution, SolutionQuality::Inaccurate))
+            }
+            OwnedStatus::MaxIterationsReached(ref solution)
+            | OwnedStatus::TimeLimitReached(ref solution)
+            | OwnedStatus::Interrupted(ref solution) => {
+                Some((solution, SolutionQuality::Truncated))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the information reported by the solver.
+    pub fn info(&self) -> &SolveInfo {
+        self.common().0
+    }
+
+    /// Returns the convergence history of the solve if recording was enabled.
+    ///
+    /// See `Status::history`.
+    pub fn history(&self) -> Option<&ConvergenceHistory> {
+        self.common().1.as_ref()
+    }
+
+    fn common(&self) -> (&SolveInfo, &Option<ConvergenceHistory>) {
+        match *self {
+            OwnedStatus::Solved(ref solution)
+            | OwnedStatus::SolvedInaccurate(ref solution)
+            | OwnedStatus::MaxIterationsReached(ref solution)
+            | OwnedStatus::TimeLimitReached(ref solution)
+            | OwnedStatus::Interrupted(ref solution)
+            | OwnedStatus::Unsolved(ref solution) => (&solution.info, &solution.history),
+            OwnedStatus::PrimalInfeasible(ref cert)
+            | OwnedStatus::PrimalInfeasibleInaccurate(ref cert) => (&cert.info, &cert.history),
+            OwnedStatus::DualInfeasible(ref cert)
+            | OwnedStatus::DualInfeasibleInaccurate(ref cert) => (&cert.info, &cert.history),
+            OwnedStatus::NonConvex(ref failure) | OwnedStatus::Unknown(_, ref failure) => {
+                (&failure.info, &failure.history)
+            }
+            OwnedStatus::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+impl OwnedSolution {
+    /// Returns the primal variables at the solution.
+    pub fn x(&self) -> &[float] {
+        &self.x
+    }
+
+    /// Returns the dual variables at the solution.
+    ///
+    /// These are the Lagrange multipliers of the constraints `l <= Ax <= u`.
+    pub fn y(&self) -> &[float] {
+        &self.y
+    }
+
+    /// Returns the status of the polish operation.
+    pub fn polish_status(&self) -> PolishStatus {
+        self.info.polish_status()
+    }
+
+    /// Returns the primal objective value.
+    pub fn obj_val(&self) -> float {
+        self.info.obj_val()
+    }
+
+    /// Returns the norm of primal residual.
+    pub fn pri_res(&self) -> float {
+        self.info.pri_res()
+    }
+
+    /// Returns the norm of dual residual.
+    pub fn dua_res(&self) -> float {
+        self.info.dua_res()
+    }
+
+    /// Consumes the solution, returning the primal and dual variables.
+    pub fn into_xy(self) -> (Vec<float>, Vec<float>) {
+        (self.x, self.y)
+    }
+}
+
+impl OwnedPrimalInfeasibilityCertificate {
+    /// Returns the certificate of primal infeasibility.
+    ///
+    /// See `PrimalInfeasibilityCertificate::delta_y`.
+    pub fn delta_y(&self) -> &[float] {
+        &self.delta_y
+    }
+}
+
+impl OwnedDualInfeasibilityCertificate {
+    /// Returns the certificate of dual infeasibility.
+    ///
+    /// See `DualInfeasibilityCertificate::delta_x`.
+    pub fn delta_x(&self) -> &[float] {
+        &self.delta_x
+    }
+}
+
+/// Returns a vector of the solution, or an empty slice if OSQP did not allocate the solution.
+pub(crate) unsafe fn solution_vec(
+    prob: &Problem,
+    vec: fn(&ffi::OSQPSolution) -> *mut float,
+    len: usize,
+) -> &[float] {
+    match (*prob.solver).solution.as_ref() {
+        Some(solution) => slice::from_raw_parts(vec(solution), len),
+        None => &[],
+    }
+}
+
+/// Converts a time recorded by OSQP, which is only recorded if profiling is enabled.
+fn add_durations(a: Option<Duration>, b: Option<Duration>) -> Option<Duration> {
+    a.and_then(|a| b.map(|b| a + b))
+}
+
+fn profiled_duration(secs: float) -> Option<Duration> {
+    if ffi::PROFILING_ENABLED {
+        Some(secs_to_duration(secs))
+    } else {
+        None
+    }
+}
+
+pub(crate) fn secs_to_duration(secs: float) -> Duration {
     let whole_secs = secs.floor() as u64;
     let nanos = (secs.fract() * 1e9) as u32;
     Duration::new(whole_secs, nanos)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tests::example_problem;
+    use {capabilities, Capabilities, Settings};
+
+    #[test]
+    fn timing_requires_profiling() {
+        let settings = Settings::default().verbose(false);
+        let mut prob = example_problem(&settings).unwrap();
+        let status = prob.solve().unwrap();
+
+        let profiling = capabilities().contains(Capabilities::PROFILING);
+        if !cfg!(feature = "system") {
+            assert_eq!(profiling, cfg!(feature = "profiling"));
+        }
+        assert_eq!(status.setup_time().is_some(), profiling);
+        assert_eq!(status.run_time().is_some(), profiling);
+        assert_eq!(status.info().solve_time().is_some(), profiling);
+        assert_eq!(status.info().update_time().is_some(), profiling);
+    }
+
+    #[test]
+    fn solve_info_outlives_status() {
+        let settings = Settings::default().verbose(false);
+        let mut prob = example_problem(&settings).unwrap();
+
+        let (info, iter, obj_val) = {
+            let status = prob.solve().unwrap();
+            let solution = status.solution().unwrap();
+            (status.info(), status.iter(), solution.obj_val())
+        };
+
+        // The problem can be modified while the info is still in use
+        prob.update_lin_cost(&[2.0, 2.0]).unwrap();
+        let next_info = prob.solve().unwrap().info();
+
+        assert_eq!(info.status(), "solved");
+        assert_eq!(info.status_val(), i64::from(ffi::OSQP_SOLVED));
+        assert_eq!(info.iter(), iter);
+        assert_eq!(info.obj_val(), obj_val);
+        assert!(info.obj_val() != next_info.obj_val());
+    }
+
+    #[test]
+    fn owned_status() {
+        use std::thread;
+
+        let settings = Settings::default().verbose(false);
+        let mut prob = example_problem(&settings).unwrap();
+
+        let first = prob.solve_owned().unwrap();
+        let first_x = first.x().unwrap().to_vec();
+
+        // The owned status is unaffected by further updates and solves
+        prob.update_bounds(&[1.0, 0.0, 0.0], &[1.0, 0.2, 0.9])
+            .unwrap();
+        let second = prob.solve().unwrap().to_owned();
+        assert_eq!(first.x().unwrap(), &first_x[..]);
+        assert!(second.x().unwrap()[0] <= 0.2 + 1e-3);
+
+        // and can be sent to another thread
+        let x = thread::spawn(move || first.x().unwrap().to_vec())
+            .join()
+            .unwrap();
+        assert_eq!(x, first_x);
+
+        let solution = second.solution().unwrap();
+        assert_eq!(solution.y().len(), 3);
+        assert_eq!(solution.obj_val(), second.info().obj_val());
+    }
+
+    #[test]
+    fn iterate_quality() {
+        let settings = Settings::default().verbose(false);
+        let mut prob = example_problem(&settings).unwrap();
+        {
+            let status = prob.solve().unwrap();
+            let (solution, quality) = status.iterate().unwrap();
+            assert_eq!(quality, SolutionQuality::Exact);
+            assert_eq!(solution.x(), status.x().unwrap());
+        }
+
+        // Stop the solver before it converges
+        let settings = Settings::default()
+            .verbose(false)
+            .max_iter(2)
+            .check_termination(Some(1))
+            .warm_starting(false);
+        let mut prob = example_problem(&settings).unwrap();
+        let status = prob.solve().unwrap();
+        assert!(status.x().is_none());
+        match status {
+            Status::MaxIterationsReached(_) => (),
+            ref other => panic!("unexpected status {:?}", other),
+        }
+        let (solution, quality) = status.iterate().unwrap();
+        assert_eq!(quality, SolutionQuality::Truncated);
+        assert_eq!(solution.x().len(), 2);
+        assert!(solution.pri_res() > 0.0);
+    }
+
+    #[test]
+    fn unrecognised_status_codes() {
+        let settings = Settings::default().verbose(false);
+        let mut prob = example_problem(&settings).unwrap();
+        prob.solve().unwrap();
+
+        // Simulate status codes added by a future version of OSQP
+        unsafe {
+            (*(*prob.solver).info).status_val = 42;
+        }
+        match Status::from_problem(&prob) {
+            Status::Unknown(42, _) => (),
+            other => panic!("unexpected status {:?}", other),
+        }
+
+        unsafe {
+            (*(*prob.solver).info).status_val = ffi::OSQP_SOLVED as ffi::osqp_int;
+            (*(*prob.solver).info).status_polish = 7;
+        }
+        let status = Status::from_problem(&prob);
+        let solution = status.solution().unwrap();
+        assert_eq!(solution.polish_status(), PolishStatus::Unknown(7));
+
+        unsafe {
+            (*(*prob.solver).info).status_val = ffi::OSQP_SIGINT as ffi::osqp_int;
+        }
+        match Status::from_problem(&prob) {
+            Status::Interrupted(solution) => assert_eq!(solution.x().len(), 2),
+            other => panic!("unexpected status {:?}", other),
+        }
+    }
+}
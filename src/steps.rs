@@ -0,0 +1,302 @@
+use osqp_sys as ffi;
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use {float, Problem, Settings, SolveError, SolveInfo, Status, UpdateError};
+
+impl Problem {
+    /// Returns an iterator which solves the problem in steps of at most `chunk` ADMM iterations.
+    ///
+    /// Each call to `next` continues from the iterate reached by the previous step and yields a
+    /// `Progress` snapshot. Iteration stops once OSQP terminates, the `max_iter` setting is
+    /// reached in total, a step fails or the condition set using `SolveSteps::stop_when` returns
+    /// true. The final status is returned by `SolveSteps::finish`.
+    ///
+    /// The `max_iter`, `warm_starting` and `time_limit` settings are changed while stepping and
+    /// restored when the iterator is finished or dropped. The `time_limit` setting applies to all
+    /// steps together.
+    ///
+    /// Each step is a separate OSQP solve which restarts its iteration count. Termination checks
+    /// and rho adaptation are therefore scheduled within each step: `chunk` should be a multiple
+    /// of `check_termination`, and rho is never adapted if `chunk` is smaller than
+    /// `adaptive_rho_interval`. Stepping can therefore take a different path to `Problem::solve`.
+    ///
+    /// Panics if `chunk` is zero.
+    pub fn solve_steps(&mut self, chunk: u32) -> SolveSteps<'_> {
+        assert!(chunk > 0, "chunk must be positive");
+        let settings = Settings::from_problem(self);
+        if !settings.get_warm_starting() {
+            self.cold_start();
+        }
+        self.history = None;
+        self.start_solve();
+        SolveSteps {
+            max_iter: settings.get_max_iter(),
+            settings,
+            prob: Some(self),
+            chunk,
+            iter: 0,
+            info: None,
+            start: Instant::now(),
+            done: false,
+            stop: None,
+        }
+    }
+}
+
+/// An iterator which advances the solver a bounded number of iterations at a time.
+///
+/// Returned by `Problem::solve_steps`.
+pub struct SolveSteps<'a> {
+    prob: Option<&'a mut Problem>,
+    /// The settings to restore once stepping is finished
+    settings: Settings,
+    chunk: u32,
+    max_iter: u32,
+    /// Total number of iterations taken by all steps
+    iter: u32,
+    /// Combined information of all steps
+    info: Option<SolveInfo>,
+    start: Instant,
+    done: bool,
+    stop: Option<StopCondition<'a>>,
+}
+
+type StopCondition<'a> = Box<dyn FnMut(&Progress) -> bool + 'a>;
+
+impl<'a> SolveSteps<'a> {
+    /// Sets a condition which stops iteration after the step for which it returns true.
+    pub fn stop_when<F: FnMut(&Progress) -> bool + 'a>(mut self, condition: F) -> SolveSteps<'a> {
+        self.stop = Some(Box::new(condition));
+        self
+    }
+
+    /// Returns the total number of iterations taken by all steps so far.
+    pub fn iter(&self) -> u32 {
+        self.iter
+    }
+
+    /// Restores the settings changed while stepping and returns the status of the last step.
+    ///
+    /// The number of iterations, rho updates and the solve, polish and run times reported by the
+    /// status are totals over all steps. The setup and update times are those measured before the
+    /// first step.
+    pub fn finish(mut self) -> Status<'a> {
+        self.restore_settings();
+        Status::from_problem(self.prob.take().unwrap())
+    }
+
+    fn step(&mut self) -> Result<Option<Progress>, StepError> {
+        let time_limit = match self.settings.get_time_limit() {
+            Some(limit) => match limit.checked_sub(self.start.elapsed()) {
+                Some(remaining) => Some(remaining),
+                // The time limit was reached by the previous steps
+                None => return Ok(None),
+            },
+            None => None,
+        };
+        let max_iter = cmp::min(self.chunk, self.max_iter - self.iter);
+        let prob = self.prob.as_mut().unwrap();
+        if prob.interrupt_requested() {
+            // The solve was cancelled between steps
+            prob.steps_interrupted = true;
+            return Ok(None);
+        }
+        prob.update_max_iter(max_iter).map_err(StepError::Update)?;
+        prob.update_warm_starting(true).map_err(StepError::Update)?;
+        prob.update_time_limit(time_limit)
+            .map_err(StepError::Update)?;
+        prob.steps_info = self.info;
+
+        prob.solve_once().map_err(StepError::Solve)?;
+        let info = unsafe { SolveInfo::from_ffi(&*(*prob.solver).info) };
+        self.iter += info.iter();
+        self.info = Some(match self.info {
+            Some(ref earlier) => earlier.followed_by(&info),
+            None => info,
+        });
+        let progress = Progress {
+            iter: self.iter,
+            obj_val: info.obj_val(),
+            pri_res: info.pri_res(),
+            dua_res: info.dua_res(),
+            rho: Settings::from_problem(prob).get_rho(),
+            elapsed: self.start.elapsed(),
+        };
+
+        // OSQP reports the best status it can when a step reaches `max_iter` so inaccurate
+        // results are only final once all iterations are used
+        let unfinished = matches!(
+            info.status_val() as ffi::osqp_status_type,
+            ffi::OSQP_MAX_ITER_REACHED
+                | ffi::OSQP_SOLVED_INACCURATE
+                | ffi::OSQP_PRIMAL_INFEASIBLE_INACCURATE
+                | ffi::OSQP_DUAL_INFEASIBLE_INACCURATE
+        );
+        if !unfinished || self.iter >= self.max_iter {
+            self.done = true;
+        }
+        Ok(Some(progress))
+    }
+
+    fn restore_settings(&mut self) {
+        if let Some(prob) = self.prob.as_mut() {
+            // Keep any rho chosen by adaptive rho while stepping. The settings were read from the
+            // solver so they are valid.
+            let settings = self
+                .settings
+                .clone()
+                .rho(Settings::from_problem(prob).get_rho());
+            let _ = prob.update_settings(&settings);
+        }
+    }
+}
+
+impl<'a> Iterator for SolveSteps<'a> {
+    type Item = Result<Progress, StepError>;
+
+    fn next(&mut self) -> Option<Result<Progress, StepError>> {
+        if self.done {
+            return None;
+        }
+        let progress = match self.step() {
+            Ok(Some(progress)) => progress,
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Err(err) => {
+                self.done = true;
+                return Some(Err(err));
+            }
+        };
+        if let Some(ref mut stop) = self.stop {
+            if stop(&progress) {
+                self.done = true;
+            }
+        }
+        Some(Ok(progress))
+    }
+}
+
+impl<'a> Drop for SolveSteps<'a> {
+    fn drop(&mut self) {
+        self.restore_settings();
+    }
+}
+
+/// An error that can occur when advancing the solver using `SolveSteps`.
+#[derive(Debug)]
+#[allow(clippy::manual_non_exhaustive)]
+pub enum StepError {
+    /// The settings used for a step could not be applied.
+    Update(UpdateError),
+    /// The solver failed.
+    Solve(SolveError),
+    // Prevent exhaustive enum matching
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl fmt::Display for StepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StepError::Update(err) => write!(f, "failed to update step settings: {}", err),
+            StepError::Solve(err) => err.fmt(f),
+            StepError::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+impl Error for StepError {}
+
+/// A snapshot of the solver state after a step of `Problem::solve_steps`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress {
+    iter: u32,
+    obj_val: float,
+    pri_res: float,
+    dua_res: float,
+    rho: float,
+    elapsed: Duration,
+}
+
+impl Progress {
+    /// Returns the total number of iterations taken by all steps so far.
+    pub fn iter(&self) -> u32 {
+        self.iter
+    }
+
+    /// Returns the primal objective value.
+    pub fn obj_val(&self) -> float {
+        self.obj_val
+    }
+
+    /// Returns the norm of the primal residual.
+    pub fn pri_res(&self) -> float {
+        self.pri_res
+    }
+
+    /// Returns the norm of the dual residual.
+    pub fn dua_res(&self) -> float {
+        self.dua_res
+    }
+
+    /// Returns the step size parameter rho used by the solver.
+    pub fn rho(&self) -> float {
+        self.rho
+    }
+
+    /// Returns the time elapsed since `Problem::solve_steps` was called.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tests::example_problem;
+
+    #[test]
+    fn solve_steps() {
+        let settings = Settings::default()
+            .verbose(false)
+            .warm_starting(false)
+            .check_termination(Some(1));
+        let mut prob = example_problem(&settings).unwrap();
+
+        let mut steps = prob.solve_steps(2);
+        let progress = steps.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert!(progress.len() > 1);
+        assert!(progress[0].iter() <= 2);
+        assert!(progress
+            .windows(2)
+            .all(|p| p[0].iter() < p[1].iter() && p[1].iter() <= p[0].iter() + 2));
+        assert_eq!(steps.iter(), progress.last().unwrap().iter());
+        let status = steps.finish();
+        assert_eq!(status.iter(), progress.last().unwrap().iter());
+        assert_eq!(status.info().iter(), status.iter());
+        let x = match status {
+            Status::Solved(solution) => solution.x(),
+            _ => panic!("expected Solved"),
+        };
+        assert!((x[0] - 0.3).abs() < 1e-3 && (x[1] - 0.7).abs() < 1e-3);
+
+        // Stop after the first step and restore the settings when the iterator is dropped
+        let progress = prob
+            .solve_steps(1)
+            .stop_when(|progress| progress.pri_res() < 1.0)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert!(progress.last().unwrap().pri_res() < 1.0);
+        assert!(progress[..progress.len() - 1]
+            .iter()
+            .all(|p| p.pri_res() >= 1.0));
+        let restored = Settings::from_problem(&prob);
+        assert_eq!(restored.get_max_iter(), settings.get_max_iter());
+        assert!(!restored.get_warm_starting());
+    }
+}